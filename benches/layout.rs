@@ -0,0 +1,84 @@
+//! Layout and hit-test cost over growing series, without a renderer
+
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use iced::{Point, Rectangle, Size, mouse, widget::canvas};
+use snowline::prelude::*;
+
+const BOUNDS: Size = Size::new(1200.0, 600.0);
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn series(len: usize) -> Vec<f64> {
+    (0..len)
+        .map(|i| (i as f64 * 0.01).sin() * 50.0 + 100.0)
+        .collect()
+}
+
+fn line_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("line_compute_layout");
+    for len in SIZES {
+        let data = series(len);
+        let cache = canvas::Cache::new();
+        let graph = LineGraph::from_slice(&data, &cache);
+
+        for zoom in [Zoom::Full, Zoom::Value(1.0), Zoom::Value(10.0)] {
+            let state = LineGraphState::new(zoom);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{zoom:?}"), len),
+                &state,
+                |b, state| b.iter(|| graph.compute_layout(black_box(BOUNDS), state)),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn line_hover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("line_hover");
+    let bounds = Rectangle::new(Point::ORIGIN, BOUNDS);
+    let position = Point::new(BOUNDS.width / 2.0, BOUNDS.height / 2.0);
+    let event = iced::Event::Mouse(mouse::Event::CursorMoved { position });
+
+    for len in SIZES {
+        let data = series(len);
+        let cache = canvas::Cache::new();
+        let graph = LineGraph::from_slice(&data, &cache);
+        let mut state = LineGraphState::new(Zoom::Value(1.0));
+
+        group.bench_function(BenchmarkId::from_parameter(len), |b| {
+            b.iter(|| {
+                // Clear so every iteration takes the full hit-test path
+                state.hovered_point = None;
+                canvas::Program::update(
+                    &graph,
+                    &mut state,
+                    black_box(&event),
+                    bounds,
+                    mouse::Cursor::Available(position),
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bar_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bar_compute_layout");
+    for len in SIZES {
+        let data = series(len);
+        let cache = canvas::Cache::new();
+        let state = BarGraphState::default();
+
+        for bins in [50, 500] {
+            let graph = BarGraph::from_slice(&data, &cache).bins(bins);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{bins} bins"), len),
+                &graph,
+                |b, graph| b.iter(|| graph.compute_layout(black_box(BOUNDS), &state)),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, line_layout, line_hover, bar_layout);
+criterion_main!(benches);
@@ -44,11 +44,9 @@ impl App {
     fn view(&self) -> iced::Element<'_, Message> {
         let text = iced::widget::text("Bar Graph - Aggregated bins, hover for values");
 
-        // Create data iterator - now just pass the values, enumeration happens internally
-        let data_iter = self.data.iter().copied();
         let graph = iced::Element::from(
             iced::widget::canvas(
-                BarGraph::new(data_iter, &self.bar_graph_cache)
+                BarGraph::from_slice(&self.data, &self.bar_graph_cache)
                     .bar_width(8.0)
                     .bar_color_scheme(bar_graph::color_scheme::BarColorScheme::performance())
                     .show_grid(true)
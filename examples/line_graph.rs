@@ -44,11 +44,9 @@ impl App {
     fn view(&self) -> iced::Element<'_, Message> {
         let text = iced::widget::text("Line Graph - Scroll to zoom, hover for values");
 
-        // Create data iterator - now just pass the values, enumeration happens internally
-        let data_iter = self.data.iter().copied();
         let graph = iced::Element::from(
             iced::widget::canvas(
-                LineGraph::new(data_iter, &self.line_graph_cache)
+                LineGraph::from_slice(&self.data, &self.line_graph_cache)
                     .show_points(true)
                     .point_radius(4.0)
                     .line_width(2.5)
@@ -6,6 +6,10 @@ use iced::{Color, Theme};
 pub struct BarColorParams<'a> {
     /// The index/position of the bar (0-based)
     pub index: usize,
+    /// A key for the bar that doesn't change with zoom and pan; see [`BarLayout::keys`]
+    ///
+    /// [`BarLayout::keys`]: super::layout::BarLayout::keys
+    pub key: usize,
     /// The value of the bar
     pub value: f64,
     /// The average value across all bars
@@ -17,6 +21,8 @@ pub struct BarColorParams<'a> {
 pub enum BarColorScheme {
     Single(Color),
     Palette(Vec<Color>),
+    /// Like [`BarColorScheme::Palette`], but cycled by [`BarColorParams::key`]
+    KeyedPalette(Vec<Color>),
     Function(Box<dyn Fn(&BarColorParams) -> Color + Send + Sync>),
 }
 
@@ -35,6 +41,13 @@ impl BarColorScheme {
                     colors[idx]
                 }
             }
+            BarColorScheme::KeyedPalette(colors) => {
+                if colors.is_empty() {
+                    Color::from_rgb(0.7, 0.7, 0.7)
+                } else {
+                    colors[params.key % colors.len()]
+                }
+            }
             BarColorScheme::Function(function) => function(params),
         };
 
@@ -59,6 +72,12 @@ impl BarColorScheme {
         Self::Palette(colors)
     }
 
+    /// Create a palette-based color scheme that cycles colors by each bar's stable
+    /// key, so a bin or category keeps its color as zoom and pan move it around
+    pub fn keyed_palette(colors: Vec<Color>) -> Self {
+        Self::KeyedPalette(colors)
+    }
+
     /// Default performance-based color scheme
     pub fn performance() -> Self {
         Self::new_function(|params| {
@@ -105,6 +124,7 @@ impl Clone for BarColorScheme {
         match self {
             BarColorScheme::Single(color) => BarColorScheme::Single(*color),
             BarColorScheme::Palette(colors) => BarColorScheme::Palette(colors.clone()),
+            BarColorScheme::KeyedPalette(colors) => BarColorScheme::KeyedPalette(colors.clone()),
             BarColorScheme::Function(_) => {
                 // Can't clone functions, so return default
                 Self::default()
@@ -1,8 +1,15 @@
 //! Drawing utilities for bar graphs
 
-use super::{BarGraph, color_scheme::BarColorParams};
+use super::{
+    BarGraph, BarLayout, BinSpan, LineOverlay, OverlayAxis, color_scheme::BarColorParams,
+    layout::BOTTOM_MARGIN,
+};
 use crate::utils::LabelFormatter;
 use crate::utils::ValueMapper;
+use crate::utils::{
+    GridLayer, MEDIAN_COLOR, MEDIAN_DASH, clamp_reference_line, estimate_text_width,
+    format_deviation, subdivisions, text_shaping,
+};
 use iced::{
     Bottom, Center, Color, Font, Pixels, Point, Rectangle, Right, Size, Theme, Top, widget::canvas,
 };
@@ -12,49 +19,51 @@ where
     I: Iterator<Item = T> + Clone + 'a,
     M: ValueMapper<T>,
 {
-    /// Draw the bars themselves
-    #[allow(clippy::too_many_arguments)]
-    pub(super) fn draw_bars(
+    /// Shaded bands for [`BarGraph::value_bands`], clipped to the bars' scale
+    pub(super) fn draw_value_bands(
         &self,
         frame: &mut canvas::Frame,
         bounds: Size,
-        visible_bars: usize,
-        values: &[f64],
-        average: f64,
-        max_value: f64,
-        theme: &Theme,
+        layout: &BarLayout,
     ) {
-        if values.is_empty() || max_value == 0.0 {
+        if layout.max_value == 0.0 {
             return;
         }
 
-        let bar_width = bounds.width / visible_bars as f32;
-        let bottom_margin = 40.0;
-        let available_height = bounds.height - bottom_margin;
-        let pixels_per_unit = available_height / max_value as f32;
+        for &(from, to, color) in &self.value_bands {
+            let low = from.min(to).max(0.0);
+            let high = from.max(to).min(layout.max_value);
+            if low >= high {
+                continue;
+            }
+            let (top, bottom) = (layout.value_y(high), layout.value_y(low));
+            frame.fill_rectangle(
+                Point::new(0.0, top),
+                Size::new(bounds.width, bottom - top),
+                color,
+            );
+        }
+    }
 
-        for (i, value) in values.iter().take(visible_bars).enumerate() {
+    /// Draw the bars themselves
+    pub(super) fn draw_bars(
+        &self,
+        frame: &mut canvas::Frame,
+        bars: &[Rectangle],
+        values: &[f64],
+        keys: &[usize],
+        average: f64,
+        theme: &Theme,
+    ) {
+        for (i, (bar, value)) in bars.iter().zip(values).enumerate() {
             let value = *value;
-
-            // Minimum bar height for zero values to be visible
-            let min_bar_height = if value == 0.0 { 3.0 } else { 0.0 };
-            let bar_height = ((value * pixels_per_unit as f64) as f32).max(min_bar_height);
-
-            // Add some padding between bars
-            let bar_padding = bar_width * 0.1;
-            let actual_bar_width = bar_width - bar_padding;
-
-            let bar = Rectangle {
-                x: i as f32 * bar_width + bar_padding / 2.0,
-                y: bounds.height - bottom_margin - bar_height,
-                width: actual_bar_width,
-                height: bar_height,
-            };
+            let key = keys.get(i).copied().unwrap_or(i);
 
             // Determine bar color
             let bar_color = {
                 let params = BarColorParams {
                     index: i,
+                    key,
                     value,
                     average,
                     theme,
@@ -81,18 +90,266 @@ where
             frame.fill_rectangle(
                 bar.position(),
                 bar.size(),
-                self.bar_color.unwrap_or(bar_color),
+                self.highlighted(
+                    value,
+                    self.bar_colors_by_key
+                        .get(&(key as u64))
+                        .copied()
+                        .or(self.bar_color)
+                        .unwrap_or(bar_color),
+                ),
+            );
+        }
+    }
+
+    /// Draw one box-and-whisker glyph per bin in place of the bars
+    pub(super) fn draw_box_plot(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        layout: &BarLayout,
+        theme: &Theme,
+    ) {
+        let summaries = &layout.summaries;
+        if summaries.is_empty() || layout.max_value == 0.0 {
+            return;
+        }
+
+        let bar_width = bounds.width / summaries.len() as f32;
+        let to_y = |value: f64| layout.value_y(value);
+
+        for (i, summary) in summaries.iter().enumerate() {
+            let params = BarColorParams {
+                index: i,
+                key: layout.keys.get(i).copied().unwrap_or(i),
+                value: summary.median,
+                average: layout.average,
+                theme,
+            };
+            let color = self.highlighted(
+                summary.median,
+                self.bar_color
+                    .unwrap_or_else(|| self.bar_color_scheme.call(&params)),
+            );
+            let stroke = canvas::Stroke::default().with_color(color).with_width(1.5);
+
+            let center_x = i as f32 * bar_width + bar_width / 2.0;
+            let box_width = bar_width * 0.6;
+            let cap_width = bar_width * 0.3;
+            let (min_y, q1_y, median_y, q3_y, max_y) = (
+                to_y(summary.min),
+                to_y(summary.q1),
+                to_y(summary.median),
+                to_y(summary.q3),
+                to_y(summary.max),
+            );
+
+            // Whiskers with end caps
+            for (from, to) in [(min_y, q1_y), (q3_y, max_y)] {
+                frame.stroke(
+                    &canvas::Path::line(Point::new(center_x, from), Point::new(center_x, to)),
+                    stroke,
+                );
+            }
+            for y in [min_y, max_y] {
+                frame.stroke(
+                    &canvas::Path::line(
+                        Point::new(center_x - cap_width / 2.0, y),
+                        Point::new(center_x + cap_width / 2.0, y),
+                    ),
+                    stroke,
+                );
+            }
+
+            // Interquartile box, at least a hairline tall so tight bins stay visible
+            let top_left = Point::new(center_x - box_width / 2.0, q3_y);
+            let size = Size::new(box_width, (q1_y - q3_y).max(1.0));
+            frame.fill_rectangle(top_left, size, color.scale_alpha(0.4));
+            frame.stroke(&canvas::Path::rectangle(top_left, size), stroke);
+
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(center_x - box_width / 2.0, median_y),
+                    Point::new(center_x + box_width / 2.0, median_y),
+                ),
+                stroke.with_width(2.5),
+            );
+        }
+    }
+
+    /// Draw the overlay aggregation as a line through the centers of the first bars
+    pub(super) fn draw_overlay_line(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        layout: &BarLayout,
+        color: Color,
+    ) {
+        let values = &layout.overlay;
+        if values.len() < 2 || layout.max_value == 0.0 {
+            return;
+        }
+
+        let bar_width = bounds.width / layout.values.len() as f32;
+
+        let mut builder = canvas::path::Builder::new();
+        for (i, value) in values.iter().enumerate() {
+            let point = Point::new(
+                i as f32 * bar_width + bar_width / 2.0,
+                layout.value_y(*value),
             );
+            if i == 0 {
+                builder.move_to(point);
+            } else {
+                builder.line_to(point);
+            }
+        }
+
+        frame.stroke(
+            &builder.build(),
+            canvas::Stroke::default().with_color(color).with_width(2.0),
+        );
+    }
+
+    /// Draw the [`BarGraph::with_line_overlay`] series, with its axis maximum on the
+    /// right when it has its own scale
+    pub(super) fn draw_line_overlay(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        layout: &BarLayout,
+        overlay: &LineOverlay,
+    ) {
+        let points = &layout.line_overlay;
+        if points.is_empty() {
+            return;
+        }
+
+        if points.len() > 1 {
+            let path = canvas::Path::new(|builder| {
+                builder.move_to(points[0]);
+                for point in &points[1..] {
+                    builder.line_to(*point);
+                }
+            });
+            frame.stroke(
+                &path,
+                canvas::Stroke::default()
+                    .with_color(overlay.color)
+                    .with_width(2.0),
+            );
+        }
+        for point in points {
+            frame.fill(&canvas::Path::circle(*point, 2.5), overlay.color);
+        }
+
+        if self.show_labels && overlay.axis == OverlayAxis::Secondary {
+            frame.fill_text(canvas::Text {
+                content: self.labels.format_y_axis(layout.line_overlay_max),
+                position: Point::new(bounds.width - 5.0, self.top_margin + 2.0),
+                color: overlay.color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                shaping: text_shaping(self.advanced_text),
+                align_x: Right.into(),
+                align_y: Top,
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    /// Small ▲/▼ change labels above each bar, skipped where they don't fit
+    pub(super) fn draw_deltas(&self, frame: &mut canvas::Frame, layout: &BarLayout) {
+        let text_size = 9.0;
+        for (bar, delta) in layout.bars.iter().zip(&layout.deltas) {
+            if !delta.is_finite() || *delta == 0.0 {
+                continue;
+            }
+            let (arrow, color) = if *delta > 0.0 {
+                ('▲', Color::from_rgb(0.2, 0.8, 0.3))
+            } else {
+                ('▼', Color::from_rgb(0.9, 0.3, 0.3))
+            };
+            let content = format!("{arrow}{}", self.labels.format_tooltip(delta.abs()));
+            if estimate_text_width(&content, text_size) > bar.width {
+                continue;
+            }
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(bar.center_x(), (bar.y - 2.0).max(text_size)),
+                color,
+                size: Pixels(text_size),
+                font: Font::MONOSPACE,
+                shaping: text_shaping(self.advanced_text),
+                align_x: Center.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
+        }
+    }
+
+    /// Draw bar labels along the bottom
+    pub(super) fn draw_bar_labels(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        visible_bars: usize,
+        bin_spans: &[BinSpan],
+        theme: &Theme,
+    ) {
+        if visible_bars == 0 || !self.show_labels {
+            return;
+        }
+
+        let bar_width = bounds.width / visible_bars as f32;
+        let palette = theme.extended_palette();
+
+        // Bin name, else bar index or bucket lower bound in histogram mode
+        let label = |i: usize| {
+            if self.bin_label_fn.is_some() {
+                return self.bin_label(bin_spans.get(i)).unwrap_or_default();
+            }
+            match bin_spans.get(i) {
+                Some(BinSpan::Values(range)) => self.labels.format_y_axis(range.start),
+                _ => format!("{}", i),
+            }
+        };
+
+        // Thin out labels that would overlap, then to the requested cap
+        let widest = (0..visible_bars)
+            .map(|i| estimate_text_width(&label(i), 10.0))
+            .fold(0.0f32, f32::max);
+        let mut label_step = ((widest + 4.0) / bar_width).ceil().max(1.0) as usize;
+        if let Some(max) = self.max_x_labels {
+            label_step = label_step.max(visible_bars.div_ceil(max.max(1)));
+        }
+
+        for i in (0..visible_bars).step_by(label_step) {
+            let content = label(i);
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(i as f32 * bar_width + bar_width / 2.0, bounds.height - 5.0),
+                color: self.text_color(palette).scale_alpha(0.6),
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                shaping: text_shaping(self.advanced_text),
+                align_x: Center.into(),
+                align_y: Bottom,
+                ..canvas::Text::default()
+            });
         }
     }
 
-    /// Draw bar labels and hover effects
-    pub(super) fn draw_bar_labels_and_hover(
+    /// Highlight the bar under the cursor and show its tooltip, drawn over the static layer
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn draw_bar_hover(
         &self,
         frame: &mut canvas::Frame,
         bounds: Size,
         visible_bars: usize,
         values: &[f64],
+        bin_spans: &[BinSpan],
+        average: f64,
         cursor: Option<Point>,
         theme: &Theme,
     ) {
@@ -101,35 +358,16 @@ where
         }
 
         let bar_width = bounds.width / visible_bars as f32;
-        let bottom_margin = 40.0;
         let palette = theme.extended_palette();
 
         for (i, value) in values.iter().take(visible_bars).enumerate() {
             let value = *value;
 
-            // Draw bar index labels at bottom
-            if self.show_labels {
-                frame.fill_text(canvas::Text {
-                    content: format!("{}", i),
-                    position: Point::new(
-                        i as f32 * bar_width + bar_width / 2.0,
-                        bounds.height - 5.0,
-                    ),
-                    color: palette.background.base.text.scale_alpha(0.6),
-                    size: Pixels(10.0),
-                    font: Font::MONOSPACE,
-                    align_x: Center.into(),
-                    align_y: Bottom,
-                    ..canvas::Text::default()
-                });
-            }
-
-            // Highlight hovered bar
             let bar_overlay = Rectangle {
                 x: i as f32 * bar_width,
                 y: 0.0,
                 width: bar_width,
-                height: bounds.height - bottom_margin,
+                height: bounds.height - BOTTOM_MARGIN,
             };
 
             if let Some(cursor_pos) = cursor
@@ -144,17 +382,82 @@ where
                 if self.show_labels {
                     let fits = cursor_pos.y >= 10.0;
                     let label_y = if value == 0.0 {
-                        bounds.height - bottom_margin - 15.0
+                        bounds.height - BOTTOM_MARGIN - 15.0
                     } else {
                         cursor_pos.y
                     };
 
+                    // Show which original samples (or value range) the bin summarizes
+                    let content = if let Some(tooltip_fn) = &self.tooltip_fn {
+                        tooltip_fn(i, value)
+                    } else if let Some(label) = self.bin_label(bin_spans.get(i)) {
+                        format!("{} ({})", self.labels.format_tooltip(value), label)
+                    } else {
+                        match bin_spans.get(i) {
+                            Some(BinSpan::Values(range)) => format!(
+                                "{} in {}–{}",
+                                value,
+                                self.labels.format_tooltip(range.start),
+                                self.labels.format_tooltip(range.end)
+                            ),
+                            Some(BinSpan::Samples(range)) if range.is_empty() => {
+                                "no data".to_string()
+                            }
+                            Some(BinSpan::Samples(range)) if range.len() > 1 => format!(
+                                "{} (samples {}–{})",
+                                self.labels.format_tooltip(value),
+                                range.start,
+                                range.end - 1
+                            ),
+                            Some(BinSpan::Samples(range)) => format!(
+                                "{} (sample {})",
+                                self.labels.format_tooltip(value),
+                                range.start
+                            ),
+                            None => self.labels.format_tooltip(value),
+                        }
+                    };
+                    // Padding slots and histogram buckets aren't comparable to the average
+                    let comparable = match bin_spans.get(i) {
+                        Some(BinSpan::Samples(range)) => !range.is_empty(),
+                        Some(_) => false,
+                        None => true,
+                    };
+                    let content = match format_deviation(value, average) {
+                        Some(deviation)
+                            if self.hover_show_deviation
+                                && comparable
+                                && self.tooltip_fn.is_none() =>
+                        {
+                            format!("{content} ({deviation})")
+                        }
+                        _ => content,
+                    };
+
+                    // Backdrop sized to the content so long labels stay legible over bars
+                    let text_size = 12.0;
+                    let backdrop = Size::new(
+                        estimate_text_width(&content, text_size) + 8.0,
+                        text_size + 6.0,
+                    );
+                    let backdrop_y = if fits {
+                        label_y - text_size - 3.0
+                    } else {
+                        label_y - 3.0
+                    };
+                    frame.fill_rectangle(
+                        Point::new(cursor_pos.x - backdrop.width / 2.0, backdrop_y),
+                        backdrop,
+                        palette.background.base.color.scale_alpha(0.85),
+                    );
+
                     frame.fill_text(canvas::Text {
-                        content: self.labels.format_tooltip(value),
+                        content,
                         position: Point::new(cursor_pos.x, label_y),
-                        color: palette.background.base.text,
-                        size: Pixels(12.0),
+                        color: self.text_color(palette),
+                        size: Pixels(text_size),
                         font: Font::MONOSPACE,
+                        shaping: text_shaping(self.advanced_text),
                         align_x: Center.into(),
                         align_y: if fits { Bottom } else { Top },
                         ..canvas::Text::default()
@@ -165,7 +468,8 @@ where
     }
 
     /// Draw grid lines and scale labels
-    pub(super) fn draw_grid_and_scale(
+    /// Horizontal and vertical gridlines, fainter behind the bars than in front
+    pub(super) fn draw_grid_lines(
         &self,
         frame: &mut canvas::Frame,
         bounds: Size,
@@ -177,28 +481,90 @@ where
             return;
         }
 
+        let color = self
+            .grid_color
+            .unwrap_or(theme.extended_palette().background.base.text)
+            .scale_alpha(match self.grid_layer {
+                GridLayer::Behind => 0.1,
+                GridLayer::Front => 0.35,
+            });
+        let subdivision_color = color.scale_alpha(0.4);
+
+        // Horizontal lines in the chart area only
+        if self.grid_axes.horizontal() {
+            let chart_height = bounds.height - self.top_margin - BOTTOM_MARGIN;
+            let ys: Vec<f32> = self
+                .y_tick_positions(chart_height, max_value)
+                .into_iter()
+                .map(|(y, _)| self.top_margin + y)
+                .collect();
+            for y in subdivisions(&ys, self.minor_subdivisions) {
+                frame.fill_rectangle(
+                    Point::new(0.0, y),
+                    Size::new(bounds.width, 1.0),
+                    subdivision_color,
+                );
+            }
+            for y in ys {
+                frame.fill_rectangle(Point::new(0.0, y), Size::new(bounds.width, 1.0), color);
+            }
+        }
+
+        if self.grid_axes.vertical() {
+            let vertical_steps = (visible_bars / 2).clamp(1, 10);
+            let xs: Vec<f32> = (0..=vertical_steps)
+                .map(|i| bounds.width * (i as f32 / vertical_steps as f32))
+                .collect();
+            for x in subdivisions(&xs, self.minor_subdivisions) {
+                frame.fill_rectangle(
+                    Point::new(x, 0.0),
+                    Size::new(1.0, bounds.height - BOTTOM_MARGIN),
+                    subdivision_color,
+                );
+            }
+            for x in xs {
+                frame.fill_rectangle(
+                    Point::new(x, 0.0),
+                    Size::new(1.0, bounds.height - BOTTOM_MARGIN),
+                    color,
+                );
+            }
+        }
+    }
+
+    /// Y-axis value labels and the zero baseline, drawn over the bars
+    pub(super) fn draw_grid_and_scale(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        max_value: f64,
+        theme: &Theme,
+    ) {
+        if !self.show_grid {
+            return;
+        }
+
         let palette = theme.extended_palette();
-        let bottom_margin = 40.0;
 
-        // Draw horizontal grid lines in the chart area only
-        let grid_steps = 5;
-        for i in 0..=grid_steps {
-            let y = (bounds.height - bottom_margin) * (i as f32 / grid_steps as f32);
-            frame.fill_rectangle(
-                Point::new(0.0, y),
-                Size::new(bounds.width, 1.0),
-                palette.background.base.text.scale_alpha(0.1),
-            );
+        let chart_height = bounds.height - self.top_margin - BOTTOM_MARGIN;
+        for (y, grid_value) in self.y_tick_positions(chart_height, max_value) {
+            let y = self.top_margin + y;
 
-            // Add value labels on the left
+            // Add value labels on the left; histogram counts carry no unit
             if self.show_labels {
-                let grid_value = max_value * (1.0 - i as f64 / grid_steps as f64);
+                let content = if self.histogram.is_some() {
+                    let decimals = if grid_value.fract() == 0.0 { 0 } else { 1 };
+                    format!("{grid_value:.decimals$}")
+                } else {
+                    self.labels.format_y_axis(grid_value)
+                };
                 frame.fill_text(canvas::Text {
-                    content: self.labels.format_y_axis(grid_value),
+                    content,
                     position: Point::new(5.0, y - 2.0),
-                    color: palette.background.base.text.scale_alpha(0.6),
+                    color: self.text_color(palette).scale_alpha(0.6),
                     size: Pixels(10.0),
                     font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
                     align_y: Bottom,
                     ..canvas::Text::default()
                 });
@@ -206,23 +572,62 @@ where
         }
 
         // Draw a baseline at zero
-        let zero_y = bounds.height - bottom_margin;
+        let zero_y = bounds.height - BOTTOM_MARGIN;
         frame.fill_rectangle(
             Point::new(0.0, zero_y),
             Size::new(bounds.width, 2.0),
             palette.background.base.text.scale_alpha(0.3),
         );
+    }
 
-        // Draw vertical grid lines
-        let vertical_steps = (visible_bars / 2).clamp(1, 10);
-        for i in 0..=vertical_steps {
-            let x = bounds.width * (i as f32 / vertical_steps as f32);
-            frame.fill_rectangle(
-                Point::new(x, 0.0),
-                Size::new(1.0, bounds.height - bottom_margin),
-                palette.background.base.text.scale_alpha(0.1),
-            );
+    /// Draw the "bars of samples" readout in the top-right corner
+    pub(super) fn draw_data_info(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        visible_bars: usize,
+        total_samples: usize,
+        theme: &Theme,
+    ) {
+        let palette = theme.extended_palette();
+        frame.fill_text(canvas::Text {
+            content: format!("{} bars of {} samples", visible_bars, total_samples),
+            position: Point::new(bounds.width - 5.0, 2.0),
+            color: self.text_color(palette).scale_alpha(0.6),
+            size: Pixels(10.0),
+            font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
+            align_x: Right.into(),
+            align_y: Top,
+            ..canvas::Text::default()
+        });
+    }
+
+    /// Average ± one standard deviation as a translucent strip in the average color
+    pub(super) fn draw_average_band(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        layout: &BarLayout,
+    ) {
+        let (average, std_dev) = (layout.average, layout.std_dev);
+        if !std_dev.is_finite() || layout.max_value == 0.0 {
+            return;
         }
+
+        let y = |value: f64| {
+            layout
+                .value_y(value)
+                .clamp(self.top_margin, layout.baseline)
+        };
+        let (upper, lower) = (y(average + std_dev), y(average - std_dev));
+        frame.fill_rectangle(
+            Point::new(0.0, upper),
+            Size::new(bounds.width, lower - upper),
+            self.average_color
+                .unwrap_or(Color::from_rgb(0.0, 0.6, 1.0))
+                .scale_alpha(0.12),
+        );
     }
 
     /// Draw average line and label
@@ -230,33 +635,77 @@ where
         &self,
         frame: &mut canvas::Frame,
         bounds: Size,
-        average: f64,
-        max_value: f64,
+        layout: &BarLayout,
     ) {
-        if !self.show_labels || average <= 0.0 || max_value == 0.0 {
+        let average = layout.average;
+        if !self.show_labels || average <= 0.0 || layout.max_value == 0.0 {
             return;
         }
 
-        let bottom_margin = 40.0;
-        let available_height = bounds.height - bottom_margin;
-        let pixels_per_unit = available_height / max_value as f32;
-        let average_y = bounds.height - bottom_margin - (average * pixels_per_unit as f64) as f32;
+        let color = self.average_color.unwrap_or(Color::from_rgb(0.0, 0.6, 1.0));
+        let mut average_y = layout.value_y(average);
+        if self.clamp_average {
+            average_y = clamp_reference_line(
+                frame,
+                bounds.width - 6.0,
+                average_y,
+                self.top_margin,
+                layout.baseline,
+                color,
+            );
+        }
 
         frame.fill_rectangle(
             Point::new(0.0, average_y),
             Size::new(bounds.width, 2.0),
-            Color::from_rgb(0.0, 0.6, 1.0).scale_alpha(0.7),
+            color.scale_alpha(0.7),
         );
 
         frame.fill_text(canvas::Text {
             content: self.labels.format_average_text(average),
             position: Point::new(bounds.width - 5.0, average_y - 2.0),
-            color: Color::from_rgb(0.0, 0.6, 1.0),
+            color: self.average_label_color.unwrap_or(color),
             size: Pixels(12.0),
             font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
             align_x: Right.into(),
             align_y: Bottom,
             ..canvas::Text::default()
         });
     }
+
+    /// Draw the median as a dashed line, labelled on the left so it doesn't
+    /// collide with the average label
+    pub(super) fn draw_median_line(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        median: f64,
+        layout: &BarLayout,
+    ) {
+        if !median.is_finite() || layout.max_value == 0.0 {
+            return;
+        }
+
+        let median_y = layout.value_y(median);
+
+        for (start, end) in MEDIAN_DASH.segments(0.0, bounds.width) {
+            frame.fill_rectangle(
+                Point::new(start, median_y),
+                Size::new(end - start, 2.0),
+                MEDIAN_COLOR.scale_alpha(0.7),
+            );
+        }
+
+        frame.fill_text(canvas::Text {
+            content: format!("Med: {}", self.labels.format_y_axis(median)),
+            position: Point::new(5.0, median_y - 2.0),
+            color: MEDIAN_COLOR,
+            size: Pixels(12.0),
+            font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
+            align_y: Bottom,
+            ..canvas::Text::default()
+        });
+    }
 }
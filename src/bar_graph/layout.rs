@@ -0,0 +1,258 @@
+//! Pure layout computation for bar graphs, shared by drawing and hit testing
+
+use std::ops::Range;
+
+use super::{BarGraph, OverlayAxis, state::BarGraphState};
+use crate::utils::{FiveNumberSummary, ValueMapper, mean_std_dev};
+use iced::{Point, Rectangle, Size};
+
+/// Space below the bars reserved for index labels, in pixels
+pub const BOTTOM_MARGIN: f32 = 40.0;
+
+/// What a drawn bar summarizes
+#[derive(Debug, Clone, PartialEq)]
+pub enum BinSpan {
+    /// Original sample indices (index binning)
+    Samples(Range<usize>),
+    /// Value bucket (histogram mode)
+    Values(Range<f64>),
+}
+
+/// The bins of a bar graph and where their bars land on a canvas of a given size
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarLayout {
+    /// Value of each bar: the bin aggregate, bucket count, or median in box plot mode
+    pub values: Vec<f64>,
+    /// Overlay aggregate per data bin (padding excluded), empty without an overlay
+    pub overlay: Vec<f64>,
+    /// Five-number summary per bin, empty unless drawing a box plot
+    pub summaries: Vec<FiveNumberSummary>,
+    /// What each bar covers; empty for [`BarGraph::prebinned`] graphs
+    pub spans: Vec<BinSpan>,
+    /// Key of each bar that survives zoom and pan, for [`BarColorScheme::keyed_palette`]:
+    /// the original index of prebinned bars, the bin number counted from the start
+    /// of the series for binned ones, and the position for histogram buckets
+    ///
+    /// [`BarColorScheme::keyed_palette`]: super::color_scheme::BarColorScheme::keyed_palette
+    pub keys: Vec<usize>,
+    /// Change of each data bin's value from the previous bin; NaN for the first
+    pub deltas: Vec<f64>,
+    /// Filled rectangle of each bar
+    pub bars: Vec<Rectangle>,
+    /// Points of the [`BarGraph::with_line_overlay`] line, empty without one
+    pub line_overlay: Vec<Point>,
+    /// Top of the line overlay's scale: its own maximum on a secondary axis,
+    /// otherwise `max_value`
+    pub line_overlay_max: f64,
+    pub max_value: f64,
+    /// Y of the zero line, where bars start
+    pub baseline: f32,
+    /// Pixels per unit of value on the bars' scale
+    pub pixels_per_unit: f32,
+    pub average: f64,
+    /// Population standard deviation of the visible values
+    pub std_dev: f64,
+    /// Samples in the visible (zoomed) window
+    pub total_samples: usize,
+}
+
+impl BarLayout {
+    /// Y of `value` on the bars' scale, so lines and bands drawn over the bars
+    /// stay aligned with them
+    pub fn value_y(&self, value: f64) -> f32 {
+        self.baseline - value as f32 * self.pixels_per_unit
+    }
+}
+
+impl<'a, I, T, M> BarGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Bin the data and place the bars for a canvas of `bounds`.
+    ///
+    /// This is what `draw` renders; it needs no renderer, so it can be used to
+    /// inspect or test the layout directly. Returns `None` when there is nothing
+    /// to plot (no data, or every bin is zero).
+    ///
+    /// When zoomed, only the visible window of samples is binned, into as many
+    /// bins as the full view would use.
+    pub fn compute_layout(&self, bounds: Size, state: &BarGraphState) -> Option<BarLayout> {
+        // Collect raw values via the mapper
+        let all: Vec<f64> = self
+            .datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .collect();
+
+        let window = Self::visible_window(all.len(), state.zoom);
+        let offset = window.start;
+        let values_all = &all[window];
+        if values_all.is_empty() {
+            return None;
+        }
+
+        // Aggregate into bins, or count into value buckets for a histogram
+        let (values, overlay, mut spans): (Vec<f64>, Vec<f64>, Vec<BinSpan>) = if self.prebinned {
+            (values_all.to_vec(), Vec::new(), Vec::new())
+        } else if let Some(buckets) = self.histogram_bucket_count(bounds.width) {
+            let (counts, ranges) = Self::value_buckets(values_all, buckets);
+            let spans = ranges.into_iter().map(BinSpan::Values).collect();
+            (counts, Vec::new(), spans)
+        } else {
+            let bin_size = self.bin_size(values_all.len(), bounds.width);
+            let (binned, overlay) = match self.overlay_aggregator {
+                // Both aggregations in a single pass over the bins
+                Some((overlay_kind, _)) => values_all
+                    .chunks(bin_size)
+                    .map(|bin| (self.bin_aggregator.apply(bin), overlay_kind.apply(bin)))
+                    .unzip(),
+                None => (self.aggregate_bins(values_all, bin_size), Vec::new()),
+            };
+            let spans = Self::bin_ranges(values_all.len(), bin_size)
+                .into_iter()
+                .map(|range| BinSpan::Samples(range.start + offset..range.end + offset))
+                .collect();
+            (binned, overlay, spans)
+        };
+        if values.is_empty() {
+            return None;
+        }
+
+        // Box plots replace the aggregate with each bin's median
+        let summaries: Vec<FiveNumberSummary> = if self.box_plot {
+            spans
+                .iter()
+                .filter_map(|span| match span {
+                    BinSpan::Samples(range) => Some(
+                        FiveNumberSummary::from_samples(&all[range.clone()]).unwrap_or_default(),
+                    ),
+                    BinSpan::Values(_) => None,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let mut values = if summaries.is_empty() {
+            values
+        } else {
+            summaries.iter().map(|s| s.median).collect()
+        };
+
+        let (average, std_dev) = mean_std_dev(&values);
+        // Scale to fit the overlay too so its line never leaves the chart
+        let line_values = match &self.line_overlay {
+            Some(line) => &line.values[Self::visible_window(line.values.len(), state.zoom)],
+            None => &[][..],
+        };
+        let line_max = line_values.iter().fold(0.0f64, |a, &b| a.max(b));
+        let shared_line = self
+            .line_overlay
+            .as_ref()
+            .is_some_and(|line| line.axis == OverlayAxis::Shared);
+        let max_value = values
+            .iter()
+            .chain(&overlay)
+            .chain(summaries.iter().map(|s| &s.max))
+            .fold(0.0f64, |a, &b| a.max(b))
+            .max(if shared_line { line_max } else { 0.0 })
+            * (1.0 + self.range_padding);
+        if max_value == 0.0 {
+            return None;
+        }
+
+        let deltas = std::iter::once(f64::NAN)
+            .chain(values.windows(2).map(|pair| pair[1] - pair[0]))
+            .collect();
+
+        // Empty slots after the data, drawn without a bar
+        let data_bins = values.len();
+        if self.pads_bins() {
+            let slots = self.padded_bin_count(bounds.width).max(data_bins);
+            let total = all.len();
+            values.resize(slots, 0.0);
+            spans.resize(slots, BinSpan::Samples(total..total));
+        }
+
+        let keys = if self.prebinned {
+            (offset..offset + values.len()).collect()
+        } else {
+            let bin_len = match spans.first() {
+                Some(BinSpan::Samples(range)) => range.len().max(1),
+                _ => 1,
+            };
+            spans
+                .iter()
+                .enumerate()
+                .map(|(i, span)| match span {
+                    BinSpan::Samples(range) => range.start / bin_len,
+                    BinSpan::Values(_) => i,
+                })
+                .collect()
+        };
+
+        let bar_width = bounds.width / values.len() as f32;
+        let available_height = bounds.height - self.top_margin - BOTTOM_MARGIN;
+        let pixels_per_unit = available_height / max_value as f32;
+        let baseline = bounds.height - BOTTOM_MARGIN;
+        let bars = values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                // Minimum bar height for zero values to be visible (but not for padding)
+                let min_bar_height = if *value == 0.0 && i < data_bins {
+                    3.0
+                } else {
+                    0.0
+                };
+                let bar_height = ((value * pixels_per_unit as f64) as f32).max(min_bar_height);
+
+                // Add some padding between bars
+                let bar_padding = bar_width * 0.1;
+                Rectangle {
+                    x: i as f32 * bar_width + bar_padding / 2.0,
+                    y: baseline - bar_height,
+                    width: bar_width - bar_padding,
+                    height: bar_height,
+                }
+            })
+            .collect();
+
+        // Line overlay points centered in equal slots, like the bars
+        let line_overlay_max = if shared_line { max_value } else { line_max };
+        let line_overlay = if line_overlay_max > 0.0 {
+            let slot = bounds.width / line_values.len().max(1) as f32;
+            let pixels_per_unit = available_height / line_overlay_max as f32;
+            line_values
+                .iter()
+                .enumerate()
+                .map(|(i, value)| {
+                    Point::new(
+                        (i as f32 + 0.5) * slot,
+                        baseline - (*value as f32 * pixels_per_unit),
+                    )
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Some(BarLayout {
+            values,
+            overlay,
+            summaries,
+            spans,
+            keys,
+            deltas,
+            bars,
+            line_overlay,
+            line_overlay_max,
+            max_value,
+            baseline,
+            pixels_per_unit,
+            average,
+            std_dev,
+            total_samples: values_all.len(),
+        })
+    }
+}
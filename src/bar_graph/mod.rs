@@ -1,5 +1,7 @@
+use std::{cell::Cell, collections::HashMap, ops::Range};
+
 pub use canvas::Cache;
-use iced::{Color, Event, Rectangle, Renderer, Theme, mouse, widget::canvas};
+use iced::{Color, Event, Point, Rectangle, Renderer, Theme, mouse, widget::canvas};
 
 // Make modules public for prelude access, but don't re-export types here
 pub mod color_scheme;
@@ -11,9 +13,26 @@ use state::BarGraphState;
 // Import drawing utilities
 mod drawing;
 
+pub mod layout;
+use layout::BOTTOM_MARGIN;
+pub use layout::{BarLayout, BinSpan};
+
 // Re-export the shared interaction type for backward compatibility
 pub use crate::utils::BarInteraction as Interaction;
-use crate::utils::{DefaultMap, ValueMapper};
+use crate::utils::{
+    Corner, DefaultMap, GridAxes, GridLayer, ValueMapper, draw_loading_overlay, draw_watermark,
+    format_value, median, nice_ticks, text_shaping,
+};
+use crate::zoom::Zoom;
+
+/// Narrowest bar (in pixels) that binning will produce; more bins are merged
+pub const MIN_BAR_PIXELS: f32 = 2.0;
+
+/// Builds the hover label for a bar from its index and value
+pub type TooltipFn = Box<dyn Fn(usize, f64) -> String + Send + Sync>;
+
+/// Names a bin from what it covers, e.g. the time range of its samples
+pub type BinLabelFn = Box<dyn Fn(&BinSpan) -> String + Send + Sync>;
 
 #[derive(Debug, Clone, Copy)]
 pub enum BinAggregator {
@@ -22,6 +41,41 @@ pub enum BinAggregator {
     Max,
 }
 
+/// Which scale a [`BarGraph::with_line_overlay`] line is drawn against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlayAxis {
+    /// The bars' scale, which grows to fit the line too
+    Shared,
+    /// Its own scale from zero to the line's maximum, labelled on the right
+    #[default]
+    Secondary,
+}
+
+/// A separate series drawn as a line over the bars, e.g. a rate over counts
+#[derive(Debug, Clone)]
+pub struct LineOverlay {
+    pub values: Vec<f64>,
+    pub color: Color,
+    pub axis: OverlayAxis,
+}
+
+impl BinAggregator {
+    /// Reduce one bin's samples to a single value
+    pub fn apply(self, samples: &[f64]) -> f64 {
+        match self {
+            BinAggregator::Average => {
+                let sum: f64 = samples.iter().sum();
+                sum / samples.len() as f64
+            }
+            BinAggregator::Sum => samples.iter().sum(),
+            BinAggregator::Max => samples
+                .iter()
+                .cloned()
+                .fold(f64::NEG_INFINITY, |a, b| a.max(b)),
+        }
+    }
+}
+
 #[allow(missing_debug_implementations)]
 pub struct BarGraph<'a, I, T, M = DefaultMap>
 where
@@ -30,14 +84,51 @@ where
     pub datapoints: I,
     pub cache: &'a canvas::Cache,
     pub bar_color: Option<Color>,
+    pub bar_colors_by_key: HashMap<u64, Color>,
     pub bar_width: f32,
     pub show_grid: bool,
+    pub value_bands: Vec<(f64, f64, Color)>,
+    pub highlight_above: Option<(f64, Color)>,
+    pub grid_axes: GridAxes,
+    pub grid_layer: GridLayer,
+    pub grid_color: Option<Color>,
+    pub minor_subdivisions: usize,
     pub show_labels: bool,
+    pub max_x_labels: Option<usize>,
+    pub label_color: Option<Color>,
+    pub average_color: Option<Color>,
+    pub average_label_color: Option<Color>,
+    pub show_data_info: bool,
+    pub loading: bool,
+    pub clear_background: bool,
+    pub background_color: Option<Color>,
+    pub watermark: Option<(String, Corner, f32)>,
+    pub advanced_text: bool,
+    pub show_median: bool,
+    pub show_deltas: bool,
+    pub clamp_average: bool,
+    pub average_band: bool,
+    pub sticky_hover: bool,
+    pub hover_show_deviation: bool,
     pub base_bars: f32, // Target number of bars (bins)
+    pub pad_bins: bool,
+    pub zoom_enabled: bool,
+    pub zoom_max: f32,
+    pub top_margin: f32, // Headroom above the tallest bar
+    pub range_padding: f64,
     pub bar_color_scheme: BarColorScheme,
     pub mapper: M,
     pub bin_aggregator: BinAggregator,
+    pub overlay_aggregator: Option<(BinAggregator, Color)>,
+    pub line_overlay: Option<LineOverlay>,
     pub labels: LabelConfig,
+    pub tooltip_fn: Option<TooltipFn>,
+    pub bin_label_fn: Option<BinLabelFn>,
+    pub y_ticks: Option<Vec<f64>>,
+    pub nice_ticks: bool,
+    pub histogram: Option<usize>,
+    pub prebinned: bool,
+    pub box_plot: bool,
 }
 
 impl<'a, I, T, M> BarGraph<'a, I, T, M>
@@ -50,14 +141,51 @@ where
             datapoints,
             cache,
             bar_color: None,
+            bar_colors_by_key: HashMap::new(),
             bar_width: 2.0,
             show_grid: true,
+            value_bands: Vec::new(),
+            highlight_above: None,
+            grid_axes: GridAxes::Both,
+            grid_layer: GridLayer::Behind,
+            grid_color: None,
+            minor_subdivisions: 0,
             show_labels: true,
+            max_x_labels: None,
+            label_color: None,
+            average_color: None,
+            average_label_color: None,
+            show_data_info: false,
+            loading: false,
+            clear_background: true,
+            background_color: None,
+            watermark: None,
+            advanced_text: false,
+            show_median: false,
+            show_deltas: false,
+            clamp_average: true,
+            average_band: false,
+            sticky_hover: false,
+            hover_show_deviation: false,
             base_bars: 50.0,
+            pad_bins: false,
+            zoom_enabled: false,
+            zoom_max: 20.0,
+            top_margin: 0.0,
+            range_padding: 0.0,
             bar_color_scheme: BarColorScheme::default(),
             mapper,
             bin_aggregator: BinAggregator::Average,
+            overlay_aggregator: None,
+            line_overlay: None,
             labels: LabelConfig::default(),
+            tooltip_fn: None,
+            bin_label_fn: None,
+            y_ticks: None,
+            nice_ticks: true,
+            histogram: None,
+            prebinned: false,
+            box_plot: false,
         }
     }
 
@@ -66,6 +194,15 @@ where
         self
     }
 
+    /// Fixed colors for specific bars by their stable key (the bin number, or the
+    /// sample index for prebinned data), e.g. one color per category. Takes
+    /// precedence over [`bar_color`](Self::bar_color); unmapped keys fall back to
+    /// the color scheme.
+    pub fn bar_colors_by_key(mut self, colors: HashMap<u64, Color>) -> Self {
+        self.bar_colors_by_key = colors;
+        self
+    }
+
     pub fn bar_width(mut self, width: f32) -> Self {
         self.bar_width = width;
         self
@@ -76,28 +213,257 @@ where
         self
     }
 
+    /// Shade `(from, to, color)` value ranges behind the bars
+    pub fn value_bands(mut self, bands: Vec<(f64, f64, Color)>) -> Self {
+        self.value_bands = bands;
+        self
+    }
+
+    /// Color bars above `threshold` with `color`
+    pub fn highlight_above(mut self, threshold: f64, color: Color) -> Self {
+        self.highlight_above = Some((threshold, color));
+        self
+    }
+
+    /// `color`, or the [`highlight_above`](Self::highlight_above) color if `value` exceeds its threshold
+    fn highlighted(&self, value: f64, color: Color) -> Color {
+        match self.highlight_above {
+            Some((threshold, highlight)) if value > threshold => highlight,
+            _ => color,
+        }
+    }
+
+    /// Draw only horizontal or only vertical grid lines (default both)
+    pub fn grid_axes(mut self, axes: GridAxes) -> Self {
+        self.grid_axes = axes;
+        self
+    }
+
+    /// Draw the grid over the bars instead of behind them (default behind)
+    pub fn grid_layer(mut self, layer: GridLayer) -> Self {
+        self.grid_layer = layer;
+        self
+    }
+
+    /// Color for the grid lines (default: the theme's text color)
+    pub fn grid_color(mut self, color: Color) -> Self {
+        self.grid_color = Some(color);
+        self
+    }
+
+    /// Fainter lines splitting each grid cell `subdivisions + 1` ways (default 0)
+    pub fn minor_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.minor_subdivisions = subdivisions;
+        self
+    }
+
     pub fn show_labels(mut self, show: bool) -> Self {
         self.show_labels = show;
         self
     }
 
+    /// Draw at most this many X-axis labels, on every k-th bar. Labels are
+    /// already thinned so they don't overlap; this caps them further.
+    pub fn max_x_labels(mut self, max: usize) -> Self {
+        self.max_x_labels = Some(max);
+        self
+    }
+
+    /// Color for axis labels, titles and readouts, overriding the theme's text color
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.label_color = Some(color);
+        self
+    }
+
+    /// Color of the average line and its marker, instead of the default blue
+    pub fn average_color(mut self, color: Color) -> Self {
+        self.average_color = Some(color);
+        self
+    }
+
+    /// Color of the average label text, independent of the line; defaults to the line color
+    pub fn average_label_color(mut self, color: Color) -> Self {
+        self.average_label_color = Some(color);
+        self
+    }
+
+    /// Show a readout of how many bars summarize how many samples
+    pub fn show_data_info(mut self, show: bool) -> Self {
+        self.show_data_info = show;
+        self
+    }
+
+    /// Dim the bars under a "Loading…" message and ignore input while data is fetched
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Fill the canvas with the background color before drawing (default on)
+    pub fn clear_background(mut self, clear: bool) -> Self {
+        self.clear_background = clear;
+        self
+    }
+
+    /// Color used by [`clear_background`](Self::clear_background), instead of the theme background
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Faint `text` in a `corner` of the canvas at `opacity` of the label color
+    pub fn watermark(mut self, text: impl Into<String>, corner: Corner, opacity: f32) -> Self {
+        self.watermark = Some((text.into(), corner, opacity.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Shape labels and tooltips per [`text_shaping`](crate::utils::text_shaping)
+    pub fn advanced_text(mut self, advanced: bool) -> Self {
+        self.advanced_text = advanced;
+        self
+    }
+
+    /// Pin an off-scale average line to the plot edge with an arrow (default on)
+    pub fn clamp_average(mut self, clamp: bool) -> Self {
+        self.clamp_average = clamp;
+        self
+    }
+
+    /// Shade one standard deviation either side of the average
+    pub fn average_band(mut self, band: bool) -> Self {
+        self.average_band = band;
+        self
+    }
+
+    /// Draw the median of the bar values as a dashed reference line next to the
+    /// average, to show skew at a glance
+    pub fn show_median(mut self, show: bool) -> Self {
+        self.show_median = show;
+        self
+    }
+
+    /// Label each bar with its change from the previous one, ▲ green for a rise
+    /// and ▼ red for a fall, where the bars are wide enough
+    pub fn show_deltas(mut self, show: bool) -> Self {
+        self.show_deltas = show;
+        self
+    }
+
+    /// Keep the last hovered bar highlighted after the cursor leaves
+    pub fn sticky_hover(mut self, sticky: bool) -> Self {
+        self.sticky_hover = sticky;
+        self
+    }
+
+    /// Add the hovered bar's deviation from the average to its tooltip
+    pub fn hover_show_deviation(mut self, show: bool) -> Self {
+        self.hover_show_deviation = show;
+        self
+    }
+
     pub fn base_bars(mut self, bars: f32) -> Self {
         self.base_bars = bars;
         self
     }
 
+    /// Headroom above the tallest bar as a fraction of its value (e.g. 0.05), in
+    /// data units rather than the pixels of [`BarGraph::top_margin`]
+    pub fn range_padding(mut self, fraction: f64) -> Self {
+        self.range_padding = fraction.max(0.0);
+        self
+    }
+
+    /// Reserve space above the tallest bar, e.g. for value labels or a title
+    pub fn top_margin(mut self, margin: f32) -> Self {
+        self.top_margin = margin.max(0.0);
+        self
+    }
+
+    /// Always draw the requested number of bins, leaving empty slots after the data
+    /// instead of collapsing to fewer bars (e.g. to align with a fixed time grid).
+    ///
+    /// Doesn't apply to histograms, box plots or prebinned graphs.
+    pub fn pad_bins(mut self, pad: bool) -> Self {
+        self.pad_bins = pad;
+        self
+    }
+
+    /// Zoom with the mouse wheel. Zooming re-bins the visible window into the same
+    /// number of bins rather than showing a subset of them, so zooming in reveals
+    /// finer detail. Off by default so the graph doesn't capture scrolling.
+    pub fn zoom_enabled(mut self, enabled: bool) -> Self {
+        self.zoom_enabled = enabled;
+        self
+    }
+
+    /// Maximum zoom level; at `max` the latest `1/max` of the samples is shown
+    pub fn zoom_max(mut self, max: f32) -> Self {
+        self.zoom_max = max.max(1.0);
+        self
+    }
+
     /// Set exact number of bins (bars)
     pub fn bins(mut self, count: usize) -> Self {
         self.base_bars = (count as f32).max(1.0);
         self
     }
 
+    /// Plot a histogram instead: bucket samples by value into `bucket_count` equal
+    /// ranges between the data min and max and draw how many fall in each.
+    ///
+    /// Bin aggregation, the overlay line and the average line don't apply in this mode.
+    pub fn histogram(mut self, bucket_count: usize) -> Self {
+        self.histogram = Some(bucket_count.max(1));
+        self
+    }
+
+    /// Draw each bin as a box-and-whisker glyph (min, quartiles, median, max) instead of
+    /// a solid bar. Boxes are colored by the median, which also stands in as the bin's
+    /// value for hover labels and the average line.
+    pub fn box_plot(mut self, box_plot: bool) -> Self {
+        self.box_plot = box_plot;
+        self
+    }
+
     /// Choose how to aggregate values inside each bin
     pub fn bin_aggregator(mut self, kind: BinAggregator) -> Self {
         self.bin_aggregator = kind;
         self
     }
 
+    /// Also aggregate each bin with `kind` and draw the result as a line over the bars,
+    /// e.g. average bars with a max line
+    pub fn overlay_aggregator(mut self, kind: BinAggregator, color: Color) -> Self {
+        self.overlay_aggregator = Some((kind, color));
+        self
+    }
+
+    /// Draw another series as a line over the bars, one point per value centered
+    /// in equal slots, so a series with one value per bar lines up with the bars.
+    /// It follows the bars' zoom and uses a secondary axis unless changed with
+    /// [`BarGraph::line_overlay_axis`].
+    pub fn with_line_overlay<U>(
+        mut self,
+        values: impl IntoIterator<Item = U>,
+        color: Color,
+        mapper: impl ValueMapper<U>,
+    ) -> Self {
+        self.line_overlay = Some(LineOverlay {
+            values: values.into_iter().map(|v| mapper.map(&v)).collect(),
+            color,
+            axis: OverlayAxis::default(),
+        });
+        self
+    }
+
+    /// Scale the [`BarGraph::with_line_overlay`] line against the bars or its own axis
+    pub fn line_overlay_axis(mut self, axis: OverlayAxis) -> Self {
+        if let Some(overlay) = &mut self.line_overlay {
+            overlay.axis = axis;
+        }
+        self
+    }
+
     pub fn bar_color_fn<F>(mut self, color_fn: F) -> Self
     where
         F: Fn(&BarColorParams) -> Color + Send + Sync + 'static,
@@ -127,6 +493,34 @@ where
         self
     }
 
+    /// Replace the hover label with custom text, e.g. `"bin 3: 4.2ms (n=37)"`
+    pub fn tooltip_fn<F>(mut self, tooltip_fn: F) -> Self
+    where
+        F: Fn(usize, f64) -> String + Send + Sync + 'static,
+    {
+        self.tooltip_fn = Some(Box::new(tooltip_fn));
+        self
+    }
+
+    /// Name each bin from its [`BinSpan`] in the X-axis labels and tooltips, e.g.
+    /// map a sample range to the time range it covers. Empty padding bins are left unlabelled.
+    pub fn bin_label_fn<F>(mut self, bin_label_fn: F) -> Self
+    where
+        F: Fn(&BinSpan) -> String + Send + Sync + 'static,
+    {
+        self.bin_label_fn = Some(Box::new(bin_label_fn));
+        self
+    }
+
+    /// The [`BarGraph::bin_label_fn`] label for a bin, if set and the bin holds data
+    fn bin_label(&self, span: Option<&BinSpan>) -> Option<String> {
+        let label_fn = self.bin_label_fn.as_ref()?;
+        match span? {
+            BinSpan::Samples(range) if range.is_empty() => None,
+            span => Some(label_fn(span)),
+        }
+    }
+
     pub fn scale_decimals(mut self, d: u8) -> Self {
         self.labels.scale_decimals = d;
         self
@@ -142,6 +536,39 @@ where
         self
     }
 
+    /// Format values as durations, choosing µs, ms or s by magnitude (input in ms)
+    pub fn auto_time_unit(mut self, auto: bool) -> Self {
+        self.labels.auto_time_unit = auto;
+        self
+    }
+
+    /// Group the integer digits of every label, e.g. `','` for "12,345"
+    pub fn thousands_separator(mut self, separator: char) -> Self {
+        self.labels.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Label whole-number data such as request or error counts: no decimals and
+    /// no time scaling, still with the unit suffix
+    pub fn integer_values(mut self, integer: bool) -> Self {
+        self.labels.integer_values = integer;
+        self
+    }
+
+    /// Draw Y-axis gridlines and labels at exactly these values instead of even divisions.
+    /// Ticks outside `0..=max` are skipped.
+    pub fn y_ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.y_ticks = Some(ticks);
+        self
+    }
+
+    /// Place automatic Y-axis ticks on round values (default). When disabled,
+    /// `0..=max` is split into five even divisions instead.
+    pub fn nice_ticks(mut self, nice: bool) -> Self {
+        self.nice_ticks = nice;
+        self
+    }
+
     /// Default performance-based color scheme (green for good, red for poor, orange for average)
     pub fn performance_colors(mut self) -> Self {
         self.bar_color_scheme = BarColorScheme::performance();
@@ -154,16 +581,232 @@ where
         self
     }
 
+    /// Choose the bar base color per theme, e.g. a different brand accent for light
+    /// and dark; called with `is_dark` and the palette at draw time
+    pub fn theme_color_fn<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(bool, &iced::theme::palette::Extended) -> Color + Send + Sync + 'static,
+    {
+        self.bar_color_scheme = BarColorScheme::new_function(move |params| {
+            let palette = params.theme.extended_palette();
+            color_fn(palette.is_dark, palette)
+        });
+        self
+    }
+
     /// Index-based color scheme: green for index < 6, yellow for index 6, red for index > 6
     pub fn traffic_light_colors(mut self) -> Self {
         self.bar_color_scheme = BarColorScheme::traffic_light();
         self
     }
 
+    /// Label text color: the override if set, otherwise the theme's text color
+    fn text_color(&self, palette: &iced::theme::palette::Extended) -> Color {
+        self.label_color.unwrap_or(palette.background.base.text)
+    }
+
+    /// Original sample indices behind visible bar `bar`, accounting for the zoom
+    /// window. Works out that one bin's span, so hovering doesn't re-bin the data.
+    fn bar_samples(&self, bar: usize, width: f32, state: &BarGraphState) -> Option<Range<usize>> {
+        if self.prebinned || self.histogram.is_some() {
+            return None;
+        }
+        let window = Self::visible_window(self.datapoints.clone().count(), state.zoom);
+        let bin_size = self.bin_size(window.len(), width);
+        let start = window.start + bar * bin_size;
+        (start < window.end).then(|| start..(start + bin_size).min(window.end))
+    }
+
+    /// Samples shown at `zoom`: the latest `1/zoom` of them, anchored at the end
+    fn visible_window(total: usize, zoom: Zoom) -> Range<usize> {
+        let visible = match zoom {
+            Zoom::Value(factor) if factor > 1.0 => {
+                ((total as f32 / factor).ceil() as usize).clamp(1, total.max(1))
+            }
+            _ => total,
+        };
+        total.saturating_sub(visible)..total
+    }
+
     fn desired_bins(&self, total_items: usize) -> usize {
         let desired = self.base_bars.max(1.0) as usize;
         desired.min(total_items.max(1))
     }
+
+    /// Slot count with [`BarGraph::pad_bins`]: the requested bins, capped only by width
+    fn padded_bin_count(&self, width: f32) -> usize {
+        let renderable = ((width / MIN_BAR_PIXELS).floor() as usize).max(1);
+        (self.base_bars.max(1.0) as usize).min(renderable)
+    }
+
+    /// Whether empty slots are appended after the data bins
+    fn pads_bins(&self) -> bool {
+        self.pad_bins && !self.prebinned && !self.box_plot && self.histogram.is_none()
+    }
+
+    /// Samples per bin, capping the bin count so every bar gets at least
+    /// [`MIN_BAR_PIXELS`] of the given width
+    fn bin_size(&self, total_items: usize, width: f32) -> usize {
+        let renderable = ((width / MIN_BAR_PIXELS).floor() as usize).max(1);
+        let bins = self.desired_bins(total_items).min(renderable);
+        total_items.div_ceil(bins).max(1)
+    }
+
+    /// Aggregate contiguous runs of `bin_size` samples into one value each
+    fn aggregate_bins(&self, values: &[f64], bin_size: usize) -> Vec<f64> {
+        values
+            .chunks(bin_size)
+            .map(|bin| self.bin_aggregator.apply(bin))
+            .collect()
+    }
+
+    /// Original sample index range covered by each bin
+    fn bin_ranges(total_items: usize, bin_size: usize) -> Vec<Range<usize>> {
+        (0..total_items)
+            .step_by(bin_size)
+            .map(|start| start..(start + bin_size).min(total_items))
+            .collect()
+    }
+
+    /// Bucket count for histogram mode, capped so every bar gets [`MIN_BAR_PIXELS`]
+    fn histogram_bucket_count(&self, width: f32) -> Option<usize> {
+        let renderable = ((width / MIN_BAR_PIXELS).floor() as usize).max(1);
+        self.histogram.map(|count| count.min(renderable))
+    }
+
+    /// Count finite values into `bucket_count` equal-width ranges spanning min..=max.
+    ///
+    /// The maximum lands in the last bucket. If all values are equal they share the first.
+    pub fn value_buckets(values: &[f64], bucket_count: usize) -> (Vec<f64>, Vec<Range<f64>>) {
+        let bucket_count = bucket_count.max(1);
+        let (min, max) = values
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+                (lo.min(v), hi.max(v))
+            });
+        if min > max {
+            return (Vec::new(), Vec::new());
+        }
+
+        let width = if max > min {
+            (max - min) / bucket_count as f64
+        } else {
+            1.0
+        };
+        let mut counts = vec![0.0; bucket_count];
+        for &v in values.iter().filter(|v| v.is_finite()) {
+            let bucket = (((v - min) / width) as usize).min(bucket_count - 1);
+            counts[bucket] += 1.0;
+        }
+        let ranges = (0..bucket_count)
+            .map(|i| min + i as f64 * width..min + (i + 1) as f64 * width)
+            .collect();
+        (counts, ranges)
+    }
+
+    /// Min and max of the full dataset (ignoring zoom), or `None` if it has no
+    /// finite values. Useful for giving several charts a common scale.
+    ///
+    /// Iterates the data once per call and isn't cached.
+    pub fn data_extent(&self) -> Option<(f64, f64)> {
+        self.datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .filter(|v| v.is_finite())
+            .fold(None, |extent, v| match extent {
+                None => Some((v, v)),
+                Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+            })
+    }
+
+    /// Render the aggregated bins as CSV (`index,value` header, one bin per line).
+    /// In histogram mode the columns are `start,end,count`, one bucket per line.
+    ///
+    /// Bins are computed without the pixel-width cap, i.e. as requested via [`BarGraph::bins`].
+    pub fn export_csv(&self) -> String {
+        let values: Vec<f64> = self
+            .datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .collect();
+
+        if let Some(bucket_count) = self.histogram {
+            let mut csv = String::from("start,end,count\n");
+            let (counts, ranges) = Self::value_buckets(&values, bucket_count);
+            for (count, range) in counts.iter().zip(ranges) {
+                csv.push_str(&format!("{},{},{count}\n", range.start, range.end));
+            }
+            return csv;
+        }
+
+        let mut csv = String::from("index,value\n");
+        if values.is_empty() {
+            return csv;
+        }
+
+        let bin_size = if self.prebinned {
+            1
+        } else {
+            self.bin_size(values.len(), f32::INFINITY)
+        };
+        for (index, value) in self.aggregate_bins(&values, bin_size).iter().enumerate() {
+            csv.push_str(&format!("{index},{value}\n"));
+        }
+        csv
+    }
+
+    /// Number of bars actually drawn on a canvas of the given width.
+    ///
+    /// The requested bin count is capped by the number of datapoints and by
+    /// [`MIN_BAR_PIXELS`]; because bins are contiguous and equally sized this
+    /// can be slightly lower than requested. Iterates the data once.
+    pub fn effective_bin_count(&self, width: f32, state: &BarGraphState) -> usize {
+        let total_items = Self::visible_window(self.datapoints.clone().count(), state.zoom).len();
+        if total_items == 0 {
+            return 0;
+        }
+        if self.prebinned {
+            return total_items;
+        }
+        if let Some(buckets) = self.histogram_bucket_count(width) {
+            return buckets;
+        }
+        let bins = total_items.div_ceil(self.bin_size(total_items, width));
+        if self.pads_bins() {
+            bins.max(self.padded_bin_count(width))
+        } else {
+            bins
+        }
+    }
+
+    /// Y-axis ticks as (y position, value) pairs, top to bottom
+    fn y_tick_positions(&self, chart_height: f32, max_value: f64) -> Vec<(f32, f64)> {
+        let values = match &self.y_ticks {
+            Some(ticks) => ticks
+                .iter()
+                .copied()
+                .filter(|v| *v >= 0.0 && *v <= max_value)
+                .collect(),
+            None if self.nice_ticks => nice_ticks(0.0, max_value, 6),
+            None => {
+                let grid_steps = 5;
+                return (0..=grid_steps)
+                    .map(|i| {
+                        let y = chart_height * (i as f32 / grid_steps as f32);
+                        let value = max_value * (1.0 - i as f64 / grid_steps as f64);
+                        (y, value)
+                    })
+                    .collect();
+            }
+        };
+
+        values
+            .into_iter()
+            .rev()
+            .map(|v| (chart_height * (1.0 - (v / max_value) as f32), v))
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -172,6 +815,13 @@ pub struct LabelConfig {
     pub scale_decimals: u8,
     pub tooltip_decimals: u8,
     pub average_decimals: u8,
+    /// Treat values as milliseconds and pick µs/ms/s per label instead of `unit_suffix`
+    pub auto_time_unit: bool,
+    /// Separator inserted between groups of three integer digits, if any
+    pub thousands_separator: Option<char>,
+    /// Round every label to a whole number with `unit_suffix`, ignoring the
+    /// decimals settings and `auto_time_unit`; for counts
+    pub integer_values: bool,
 }
 
 impl Default for LabelConfig {
@@ -181,36 +831,37 @@ impl Default for LabelConfig {
             scale_decimals: 0,
             tooltip_decimals: 1,
             average_decimals: 1,
+            auto_time_unit: false,
+            thousands_separator: None,
+            integer_values: false,
         }
     }
 }
 
+impl LabelConfig {
+    fn value_text(&self, value: f64, decimals: u8) -> String {
+        format_value(
+            value,
+            decimals,
+            &self.unit_suffix,
+            self.auto_time_unit,
+            self.integer_values,
+            self.thousands_separator,
+        )
+    }
+}
+
 impl crate::utils::LabelFormatter for LabelConfig {
     fn format_y_axis(&self, value: f64) -> String {
-        format!(
-            "{v:.prec$}{unit}",
-            v = value,
-            prec = self.scale_decimals as usize,
-            unit = self.unit_suffix
-        )
+        self.value_text(value, self.scale_decimals)
     }
 
     fn format_tooltip(&self, value: f64) -> String {
-        format!(
-            "{v:.prec$}{unit}",
-            v = value,
-            prec = self.tooltip_decimals as usize,
-            unit = self.unit_suffix
-        )
+        self.value_text(value, self.tooltip_decimals)
     }
 
     fn format_average_text(&self, value: f64) -> String {
-        format!(
-            "Avg: {v:.prec$}{unit}",
-            v = value,
-            prec = self.average_decimals as usize,
-            unit = self.unit_suffix
-        )
+        format!("Avg: {}", self.value_text(value, self.average_decimals))
     }
 
     fn format_title(&self, _zoom: crate::zoom::Zoom) -> Option<String> {
@@ -239,18 +890,93 @@ where
             datapoints,
             cache,
             bar_color: None,
+            bar_colors_by_key: HashMap::new(),
             bar_width: 2.0,
             show_grid: true,
+            value_bands: Vec::new(),
+            highlight_above: None,
+            grid_axes: GridAxes::Both,
+            grid_layer: GridLayer::Behind,
+            grid_color: None,
+            minor_subdivisions: 0,
             show_labels: true,
+            max_x_labels: None,
+            label_color: None,
+            average_color: None,
+            average_label_color: None,
+            show_data_info: false,
+            loading: false,
+            clear_background: true,
+            background_color: None,
+            watermark: None,
+            advanced_text: false,
+            show_median: false,
+            show_deltas: false,
+            clamp_average: true,
+            average_band: false,
+            sticky_hover: false,
+            hover_show_deviation: false,
             base_bars: 50.0,
+            pad_bins: false,
+            zoom_enabled: false,
+            zoom_max: 20.0,
+            top_margin: 0.0,
+            range_padding: 0.0,
             bar_color_scheme: BarColorScheme::default(),
             mapper: DefaultMap,
             bin_aggregator: BinAggregator::Average,
+            overlay_aggregator: None,
+            line_overlay: None,
             labels: LabelConfig::default(),
+            tooltip_fn: None,
+            bin_label_fn: None,
+            y_ticks: None,
+            nice_ticks: true,
+            histogram: None,
+            prebinned: false,
+            box_plot: false,
+        }
+    }
+}
+
+/// A bar graph that owns its data, so only the cache needs to outlive it
+pub type OwnedBarGraph<'a, T> = BarGraph<'a, std::vec::IntoIter<T>, T>;
+
+impl<'a, T> OwnedBarGraph<'a, T>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    /// Construct from owned data, e.g. values derived inside `view`.
+    ///
+    /// The data is copied each time the graph iterates it; prefer
+    /// [`BarGraph::from_slice`] when the data already lives in app state.
+    pub fn from_vec(data: Vec<T>, cache: &'a canvas::Cache) -> Self {
+        Self::new(data.into_iter(), cache)
+    }
+}
+
+impl<'a> BarGraph<'a, std::vec::IntoIter<f64>, f64, DefaultMap> {
+    /// Construct from values that are already one per bar, e.g. aggregates from a
+    /// database `GROUP BY`. Binning is skipped entirely and every value gets its own
+    /// bar, so keep the count within what the canvas width can show.
+    pub fn prebinned(bins: Vec<f64>, cache: &'a canvas::Cache) -> Self {
+        Self {
+            prebinned: true,
+            ..Self::new(bins.into_iter(), cache)
         }
     }
 }
 
+impl<'a, T> BarGraph<'a, std::iter::Copied<std::slice::Iter<'a, T>>, T, DefaultMap>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    /// Construct from a slice without spelling out `.iter().copied()`
+    pub fn from_slice(data: &'a [T], cache: &'a canvas::Cache) -> Self {
+        Self::new(data.iter().copied(), cache)
+    }
+}
+
 impl<'a, I, T, M> canvas::Program<Interaction> for BarGraph<'a, I, T, M>
 where
     I: Iterator<Item = T> + Clone + 'a,
@@ -265,11 +991,14 @@ where
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Option<canvas::Action<Interaction>> {
+        if self.loading {
+            return None;
+        }
+
         match event {
             Event::Mouse(mouse::Event::CursorMoved { .. }) => {
                 if let Some(cursor_position) = cursor.position_in(bounds) {
-                    let total_items = self.datapoints.clone().count();
-                    let visible_bars = self.desired_bins(total_items);
+                    let visible_bars = self.effective_bin_count(bounds.width, state);
 
                     if visible_bars > 0 {
                         let bar_width = bounds.width / visible_bars as f32;
@@ -278,101 +1007,215 @@ where
                         if bar_index < visible_bars {
                             if state.hovered_bar != Some(bar_index) {
                                 state.hovered_bar = Some(bar_index);
-                                self.cache.clear();
-                                return Some(canvas::Action::publish(Interaction::BarHovered(
-                                    bar_index,
-                                )));
+                                state.overlay.clear();
+                                return Some(canvas::Action::publish(Interaction::BarHovered {
+                                    bar: bar_index,
+                                    samples: self.bar_samples(bar_index, bounds.width, state),
+                                }));
                             }
                         } else if state.hovered_bar.is_some() {
                             state.hovered_bar = None;
-                            self.cache.clear();
+                            state.overlay.clear();
                             return Some(canvas::Action::request_redraw());
                         }
                     }
-                } else if state.hovered_bar.is_some() {
+                } else if state.hovered_bar.is_some() && !self.sticky_hover {
                     state.hovered_bar = None;
-                    self.cache.clear();
+                    state.overlay.clear();
                     return Some(canvas::Action::request_redraw());
                 }
                 None
             }
-            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => state
-                .hovered_bar
-                .map(|bar_index| canvas::Action::publish(Interaction::BarClicked(bar_index))),
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                state.hovered_bar.map(|bar_index| {
+                    canvas::Action::publish(Interaction::BarClicked {
+                        bar: bar_index,
+                        samples: self.bar_samples(bar_index, bounds.width, state),
+                    })
+                })
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if self.zoom_enabled && cursor.is_over(bounds) =>
+            {
+                let (mouse::ScrollDelta::Lines { y, .. } | mouse::ScrollDelta::Pixels { y, .. }) =
+                    delta;
+                // Levels below 1x show everything, so step in whole levels from 1x
+                let current = match state.zoom {
+                    Zoom::Value(value) if value >= 1.0 => Zoom::Value(value),
+                    _ => Zoom::Value(1.0),
+                };
+                let new_zoom = if *y > 0.0 {
+                    current.increment_with_limits(self.zoom_max)
+                } else if *y < 0.0 {
+                    current.decrement_with_limits(1.0)
+                } else {
+                    return None;
+                };
+                // Already at a limit: let the parent have the scroll
+                if new_zoom == current {
+                    return None;
+                }
+                state.zoom = new_zoom;
+                state.hovered_bar = None;
+                self.cache.clear();
+                Some(canvas::Action::publish(Interaction::ZoomChanged(new_zoom)).and_capture())
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                let position = cursor.position_over(bounds)?;
+                // Padding slots hold no data, so they report no value
+                let value = state.hovered_bar.and_then(|i| {
+                    let layout = self.compute_layout(bounds.size(), state)?;
+                    match layout.spans.get(i) {
+                        Some(BinSpan::Samples(range)) if range.is_empty() => None,
+                        _ => layout.values.get(i).copied(),
+                    }
+                });
+                Some(
+                    canvas::Action::publish(Interaction::ContextMenu {
+                        index: state.hovered_bar,
+                        value,
+                        position,
+                    })
+                    .and_capture(),
+                )
+            }
             _ => None,
         }
     }
 
     fn draw(
         &self,
-        _state: &Self::State,
+        state: &Self::State,
         renderer: &Renderer,
         theme: &Theme,
         bounds: Rectangle,
         cursor: mouse::Cursor,
     ) -> Vec<canvas::Geometry> {
+        // Static layer: everything but the hover highlight. The hover layer sits on
+        // top of it, so it is rebuilt whenever this one is.
+        let rebuilt = Cell::new(false);
         let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
-            let cursor = cursor.position_in(bounds);
+            rebuilt.set(true);
             let bounds = frame.size();
-
-            // Collect raw values via the mapper
-            let values_all: Vec<f64> = self
-                .datapoints
-                .clone()
-                .map(|v| self.mapper.map(&v))
-                .collect();
-
-            if values_all.is_empty() {
-                return;
+            if self.clear_background {
+                let background = self
+                    .background_color
+                    .unwrap_or(theme.extended_palette().background.base.color);
+                frame.fill_rectangle(Point::ORIGIN, bounds, background);
             }
-
-            // Aggregate into bins
-            let desired_bins = self.desired_bins(values_all.len());
-            let bin_size = ((values_all.len() as f32) / desired_bins as f32)
-                .ceil()
-                .max(1.0) as usize;
-            let mut binned: Vec<f64> = Vec::with_capacity(desired_bins);
-            let mut i = 0;
-            while i < values_all.len() {
-                let end = (i + bin_size).min(values_all.len());
-                let slice = &values_all[i..end];
-                let value = match self.bin_aggregator {
-                    BinAggregator::Average => {
-                        let sum: f64 = slice.iter().sum();
-                        sum / slice.len() as f64
-                    }
-                    BinAggregator::Sum => slice.iter().sum(),
-                    BinAggregator::Max => slice
-                        .iter()
-                        .cloned()
-                        .fold(f64::NEG_INFINITY, |a, b| a.max(b)),
-                };
-                binned.push(value);
-                i = end;
+            if let Some((text, corner, opacity)) = &self.watermark {
+                let color = self
+                    .text_color(theme.extended_palette())
+                    .scale_alpha(*opacity);
+                draw_watermark(
+                    frame,
+                    text,
+                    *corner,
+                    color,
+                    text_shaping(self.advanced_text),
+                );
             }
 
-            let visible_bars = binned.len();
-            let average = binned.iter().copied().sum::<f64>() / visible_bars as f64;
-            let max_value = binned.iter().fold(0.0f64, |a, &b| a.max(b));
-            if max_value == 0.0 {
+            let Some(layout) = self.compute_layout(bounds, state) else {
                 return;
-            }
+            };
+            let visible_bars = layout.values.len();
+            let (average, max_value) = (layout.average, layout.max_value);
 
             // Draw all components using the modular functions
-            self.draw_bars(
+            self.draw_value_bands(frame, bounds, &layout);
+            if self.average_band && self.histogram.is_none() {
+                self.draw_average_band(frame, bounds, &layout);
+            }
+            if self.grid_layer == GridLayer::Behind {
+                self.draw_grid_lines(frame, bounds, visible_bars, max_value, theme);
+            }
+            if layout.summaries.is_empty() {
+                self.draw_bars(
+                    frame,
+                    &layout.bars,
+                    &layout.values,
+                    &layout.keys,
+                    average,
+                    theme,
+                );
+            } else {
+                self.draw_box_plot(frame, bounds, &layout, theme);
+            }
+            if self.grid_layer == GridLayer::Front {
+                self.draw_grid_lines(frame, bounds, visible_bars, max_value, theme);
+            }
+            self.draw_grid_and_scale(frame, bounds, max_value, theme);
+            if let Some((_, color)) = self.overlay_aggregator
+                && self.histogram.is_none()
+            {
+                self.draw_overlay_line(frame, bounds, &layout, color);
+            }
+            if self.histogram.is_none() {
+                self.draw_average_line(frame, bounds, &layout);
+            }
+            if self.show_median && self.histogram.is_none() {
+                // Padding slots aren't data, so leave them out of the median
+                let data: Vec<f64> = layout
+                    .values
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| {
+                        !matches!(layout.spans.get(*i), Some(BinSpan::Samples(r)) if r.is_empty())
+                    })
+                    .map(|(_, value)| *value)
+                    .collect();
+                self.draw_median_line(frame, bounds, median(&data), &layout);
+            }
+            if let Some(overlay) = &self.line_overlay {
+                self.draw_line_overlay(frame, bounds, &layout, overlay);
+            }
+            if self.show_deltas && self.histogram.is_none() {
+                self.draw_deltas(frame, &layout);
+            }
+            self.draw_bar_labels(frame, bounds, visible_bars, &layout.spans, theme);
+            if self.show_data_info {
+                self.draw_data_info(frame, bounds, visible_bars, layout.total_samples, theme);
+            }
+        });
+        if rebuilt.get() {
+            state.overlay.clear();
+        }
+
+        let hover = state.overlay.draw(renderer, bounds.size(), |frame| {
+            let Some(hovered) = state.hovered_bar else {
+                return;
+            };
+            let Some(layout) = self.compute_layout(frame.size(), state) else {
+                return;
+            };
+            // A sticky hover outlives the cursor, so anchor it mid-bar instead
+            let anchor = cursor.position_in(bounds).or_else(|| {
+                let bar_width = frame.width() / layout.values.len().max(1) as f32;
+                Some(Point::new(
+                    (hovered as f32 + 0.5) * bar_width,
+                    (frame.height() - BOTTOM_MARGIN) / 2.0,
+                ))
+            });
+            self.draw_bar_hover(
                 frame,
-                bounds,
-                visible_bars,
-                &binned,
-                average,
-                max_value,
+                frame.size(),
+                layout.values.len(),
+                &layout.values,
+                &layout.spans,
+                layout.average,
+                anchor,
                 theme,
             );
-            self.draw_grid_and_scale(frame, bounds, visible_bars, max_value, theme);
-            self.draw_average_line(frame, bounds, average, max_value);
-            self.draw_bar_labels_and_hover(frame, bounds, visible_bars, &binned, cursor, theme);
         });
 
-        vec![geometry]
+        if self.loading {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+            let palette = theme.extended_palette();
+            draw_loading_overlay(&mut frame, palette, self.text_color(palette));
+            return vec![geometry, frame.into_geometry()];
+        }
+
+        vec![geometry, hover]
     }
 }
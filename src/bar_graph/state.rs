@@ -1,6 +1,37 @@
 //! State management for bar graphs
 
+use crate::{
+    utils::{OverlayCache, ZoomableGraphState},
+    zoom::Zoom,
+};
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BarGraphState {
+    /// `Value(z)` shows the latest `1/z` of the samples, re-binned; 1.0 and
+    /// [`Zoom::Full`] both show everything
+    pub zoom: Zoom,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub hovered_bar: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) overlay: OverlayCache, // hover layer, redrawn without touching the main cache
+}
+
+impl BarGraphState {
+    pub fn new(initial_zoom: Zoom) -> Self {
+        Self {
+            zoom: initial_zoom,
+            ..Self::default()
+        }
+    }
+}
+
+impl ZoomableGraphState for BarGraphState {
+    fn zoom(&self) -> Zoom {
+        self.zoom
+    }
+
+    fn set_zoom(&mut self, zoom: Zoom) {
+        self.zoom = zoom;
+    }
 }
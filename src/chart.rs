@@ -0,0 +1,246 @@
+//! Helpers for embedding a graph as an [`Element`] without canvas boilerplate
+
+use std::{marker::PhantomData, ops::Range};
+
+use iced::{
+    Element, Length, Point, Rectangle, Renderer, Theme, mouse,
+    widget::{Action, canvas},
+    window::RedrawRequest,
+};
+
+use crate::{
+    bar_graph::BarGraph,
+    line_graph::LineGraph,
+    utils::{BarInteraction, GraphInteraction, LineInteraction, ValueMapper},
+    zoom::Zoom,
+};
+
+/// Line graph over a slice, as created by [`line_chart`]
+pub type SliceLineGraph<'a, T> = LineGraph<'a, std::iter::Copied<std::slice::Iter<'a, T>>, T>;
+
+/// Bar graph over a slice, as created by [`bar_chart`]
+pub type SliceBarGraph<'a, T> = BarGraph<'a, std::iter::Copied<std::slice::Iter<'a, T>>, T>;
+
+/// Maps a right click (item index, value, window position) to a message
+type ContextMenuFn<'a, Message> = Box<dyn Fn(Option<usize>, Option<f64>, Point) -> Message + 'a>;
+
+/// A graph wrapped with its size and message handlers, convertible into an [`Element`].
+///
+/// The graph's [`canvas::Program`] impl stays available for advanced use; this only
+/// wraps it in a canvas and maps its interactions to app messages.
+#[allow(missing_debug_implementations)]
+pub struct Chart<'a, P, I, Message> {
+    graph: P,
+    width: Length,
+    height: Length,
+    on_hover: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+    on_click: Option<Box<dyn Fn(usize) -> Message + 'a>>,
+    on_zoom: Option<Box<dyn Fn(Zoom) -> Message + 'a>>,
+    on_window_change: Option<Box<dyn Fn(Range<usize>) -> Message + 'a>>,
+    on_context_menu: Option<ContextMenuFn<'a, Message>>,
+    interaction: PhantomData<I>,
+}
+
+/// Line chart over a slice, ready to be sized and turned into an [`Element`]
+pub fn line_chart<'a, T, Message>(
+    data: &'a [T],
+    cache: &'a canvas::Cache,
+) -> Chart<'a, SliceLineGraph<'a, T>, LineInteraction, Message>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    Chart::new(LineGraph::from_slice(data, cache))
+}
+
+/// Bar chart over a slice, ready to be sized and turned into an [`Element`]
+pub fn bar_chart<'a, T, Message>(
+    data: &'a [T],
+    cache: &'a canvas::Cache,
+) -> Chart<'a, SliceBarGraph<'a, T>, BarInteraction, Message>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    Chart::new(BarGraph::from_slice(data, cache))
+}
+
+impl<'a, P, I, Message> Chart<'a, P, I, Message> {
+    /// Wrap an already configured graph
+    pub fn new(graph: P) -> Self {
+        Self {
+            graph,
+            width: Length::Fill,
+            height: Length::Fill,
+            on_hover: None,
+            on_click: None,
+            on_zoom: None,
+            on_window_change: None,
+            on_context_menu: None,
+            interaction: PhantomData,
+        }
+    }
+
+    /// Adjust the wrapped graph, e.g. `.graph(|g| g.line_color(color))`
+    pub fn graph(mut self, configure: impl FnOnce(P) -> P) -> Self {
+        self.graph = configure(self.graph);
+        self
+    }
+
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    /// Produce a message when an item (point or bar) is hovered
+    pub fn on_hover(mut self, on_hover: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_hover = Some(Box::new(on_hover));
+        self
+    }
+
+    /// Produce a message when an item (point or bar) is clicked
+    pub fn on_click(mut self, on_click: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_click = Some(Box::new(on_click));
+        self
+    }
+
+    /// Produce a message when the zoom level changes
+    pub fn on_zoom(mut self, on_zoom: impl Fn(Zoom) -> Message + 'a) -> Self {
+        self.on_zoom = Some(Box::new(on_zoom));
+        self
+    }
+
+    /// Produce a message on right click, e.g. to open a context menu at the cursor
+    pub fn on_context_menu(
+        mut self,
+        on_context_menu: impl Fn(Option<usize>, Option<f64>, Point) -> Message + 'a,
+    ) -> Self {
+        self.on_context_menu = Some(Box::new(on_context_menu));
+        self
+    }
+
+    /// Interactions without a handler are dropped
+    fn map_interaction(&self, interaction: I) -> Option<Message>
+    where
+        I: Into<GraphInteraction<I>>,
+    {
+        match interaction.into() {
+            GraphInteraction::ItemHovered(index) => self.on_hover.as_ref().map(|f| f(index)),
+            GraphInteraction::ItemClicked(index) => self.on_click.as_ref().map(|f| f(index)),
+            GraphInteraction::ZoomChanged(zoom) => self.on_zoom.as_ref().map(|f| f(zoom)),
+            GraphInteraction::WindowChanged(window) => {
+                self.on_window_change.as_ref().map(|f| f(window))
+            }
+            GraphInteraction::ContextMenu {
+                index,
+                value,
+                position,
+            } => self
+                .on_context_menu
+                .as_ref()
+                .map(|f| f(index, value, position)),
+            GraphInteraction::Custom(_) => None,
+        }
+    }
+}
+
+impl<'a, P, Message> Chart<'a, P, LineInteraction, Message> {
+    /// Produce a message when a point is hovered (alias for [`Chart::on_hover`])
+    pub fn on_point_hover(self, on_hover: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_hover(on_hover)
+    }
+
+    /// Produce a message when a point is clicked (alias for [`Chart::on_click`])
+    pub fn on_point_click(self, on_click: impl Fn(usize) -> Message + 'a) -> Self {
+        self.on_click(on_click)
+    }
+}
+
+impl<'a, I, T, M, Message> Chart<'a, LineGraph<'a, I, T, M>, LineInteraction, Message>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Produce a message with the visible window of series indices once zooming
+    /// or panning settles, e.g. to fetch just that slice of a large dataset.
+    /// Turns on [`LineGraph::report_window`].
+    pub fn on_window_change(mut self, on_change: impl Fn(Range<usize>) -> Message + 'a) -> Self {
+        self.graph = self.graph.report_window(true);
+        self.on_window_change = Some(Box::new(on_change));
+        self
+    }
+}
+
+impl<'a, P, I, Message> canvas::Program<Message> for Chart<'a, P, I, Message>
+where
+    P: canvas::Program<I>,
+    I: Into<GraphInteraction<I>>,
+{
+    type State = P::State;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<Action<Message>> {
+        let (interaction, redraw, status) = self
+            .graph
+            .update(state, event, bounds, cursor)?
+            .into_inner();
+
+        let action = match interaction.map(|i| self.map_interaction(i)) {
+            Some(Some(message)) => Action::publish(message),
+            // Publishing implies a redraw, so keep it even when the message is dropped
+            Some(None) => Action::request_redraw(),
+            None => match redraw {
+                RedrawRequest::NextFrame => Action::request_redraw(),
+                RedrawRequest::At(at) => Action::request_redraw_at(at),
+                RedrawRequest::Wait if status == iced::event::Status::Captured => Action::capture(),
+                RedrawRequest::Wait => return None,
+            },
+        };
+
+        Some(if status == iced::event::Status::Captured {
+            action.and_capture()
+        } else {
+            action
+        })
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        self.graph.draw(state, renderer, theme, bounds, cursor)
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        self.graph.mouse_interaction(state, bounds, cursor)
+    }
+}
+
+impl<'a, P, I, Message> From<Chart<'a, P, I, Message>> for Element<'a, Message>
+where
+    P: canvas::Program<I> + 'a,
+    I: Into<GraphInteraction<I>> + 'a,
+    Message: 'a,
+{
+    fn from(chart: Chart<'a, P, I, Message>) -> Self {
+        let (width, height) = (chart.width, chart.height);
+        canvas(chart).width(width).height(height).into()
+    }
+}
@@ -0,0 +1,33 @@
+//! Plotters-style builder aliases
+//!
+//! Thin layer over the existing builders for users migrating from `plotters`.
+//! Nothing here adds behavior; each method forwards to the core API.
+
+use crate::{bar_graph::BarGraph, line_graph::LineGraph, utils::ValueMapper};
+
+impl<'a, I, T, M> LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Set the chart caption, shown as the title (alias for [`LineGraph::title_text`])
+    pub fn caption(self, caption: &str) -> Self {
+        self.title_text(Some(caption.to_string()))
+    }
+
+    /// Describe the Y axis, shown as the value unit suffix (alias for [`LineGraph::unit_suffix`])
+    pub fn y_desc(self, desc: &str) -> Self {
+        self.unit_suffix(desc)
+    }
+}
+
+impl<'a, I, T, M> BarGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Describe the Y axis, shown as the value unit suffix (alias for [`BarGraph::unit_suffix`])
+    pub fn y_desc(self, desc: &str) -> Self {
+        self.unit_suffix(desc)
+    }
+}
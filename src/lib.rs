@@ -3,24 +3,33 @@
 //!
 
 pub mod bar_graph;
+pub mod chart;
+#[cfg(feature = "compat")]
+pub mod compat;
 pub mod line_graph;
+pub mod panel;
 pub mod utils;
 pub mod zoom;
 
+pub use chart::{bar_chart, line_chart};
+
 pub mod prelude {
     pub use crate::{
         bar_graph::{
-            BarGraph,
+            BarGraph, BarLayout, OwnedBarGraph,
             color_scheme::{BarColorParams, BarColorScheme},
             state::BarGraphState,
         },
+        chart::{Chart, bar_chart, line_chart},
         line_graph::{
-            LineGraph,
+            LineGraph, LineLayout, OwnedLineGraph,
             color_scheme::{PointColorParams, PointColorScheme},
-            state::LineGraphState,
+            state::{LineGraphState, SharedView},
         },
+        panel::StackedPanel,
         utils::{
-            BarInteraction, GraphInteraction, GridConfig, LineInteraction, ZoomableGraphState,
+            BarInteraction, Corner, DashPattern, GraphInteraction, GridAxes, GridConfig, GridLayer,
+            LineInteraction, ShadowConfig, TooltipStyle, ZoomableGraphState,
             calculate_visible_range, draw_average_line, draw_grid, draw_y_axis_labels,
         },
         zoom::Zoom,
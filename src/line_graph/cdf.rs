@@ -0,0 +1,247 @@
+//! Cumulative distribution layout for line graphs
+
+use std::{
+    hash::{DefaultHasher, Hasher},
+    rc::Rc,
+};
+
+use super::{LineGraph, XScale, layout::CHART_PADDING, state::LineGraphState};
+use crate::utils::{LabelFormatter, ValueMapper, draw_tooltip, text_shaping};
+use iced::{
+    Bottom, Center, Color, Font, Pixels, Point, Rectangle, Right, Size, Theme, widget::canvas,
+};
+
+impl<'a, I, T, M> LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Finite values of the whole series, sorted. The sort is kept in `state` and
+    /// reused until the values change, so hovering costs one pass over the data
+    /// to fingerprint it rather than a sort.
+    fn cdf_sorted(&self, state: &LineGraphState) -> Rc<[f64]> {
+        let mut values: Vec<f64> = self
+            .datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .filter(|v| v.is_finite())
+            .collect();
+        let mut hasher = DefaultHasher::new();
+        for value in &values {
+            hasher.write_u64(value.to_bits());
+        }
+        let fingerprint = hasher.finish();
+
+        if let Some((seen, sorted)) = &*state.cdf_sorted.borrow()
+            && *seen == fingerprint
+        {
+            return sorted.clone();
+        }
+        values.sort_by(f64::total_cmp);
+        let sorted: Rc<[f64]> = values.into();
+        *state.cdf_sorted.borrow_mut() = Some((fingerprint, sorted.clone()));
+        sorted
+    }
+
+    /// Plot position of the `rank`-th sorted value: x is the value, y the fraction
+    /// of samples less than or equal to it
+    fn cdf_point(
+        &self,
+        sorted: &[f64],
+        (min, max): (f64, f64),
+        rank: usize,
+        plot: Rectangle,
+    ) -> Point {
+        let fraction = (rank + 1) as f32 / sorted.len() as f32;
+        Point::new(
+            self.cdf_x(sorted[rank], (min, max), plot),
+            plot.y + plot.height * (1.0 - fraction),
+        )
+    }
+
+    /// Horizontal position of `value` on the value axis
+    fn cdf_x(&self, value: f64, (min, max): (f64, f64), plot: Rectangle) -> f32 {
+        plot.x + self.x_scale.fraction(value.max(min), min, max) * plot.width
+    }
+
+    /// Ends of the value axis; on a log scale the lower end is the smallest
+    /// positive value, which zero and negative values are pinned to
+    fn cdf_x_extent(&self, sorted: &[f64]) -> Option<(f64, f64)> {
+        let max = *sorted.last()?;
+        let min = match self.x_scale {
+            XScale::Linear => *sorted.first()?,
+            XScale::Log10 => *sorted.iter().find(|v| **v > 0.0)?,
+        };
+        Some((min, max))
+    }
+
+    /// Percentage of samples at or below the `rank`-th sorted value
+    fn cdf_percent(rank: usize, count: usize) -> f64 {
+        (rank + 1) as f64 / count as f64 * 100.0
+    }
+
+    /// Value at sorted `rank`
+    pub(super) fn cdf_value(&self, state: &LineGraphState, rank: usize) -> Option<f64> {
+        self.cdf_sorted(state).get(rank).copied()
+    }
+
+    /// Sorted rank of the point closest to the cursor horizontally
+    pub(super) fn find_nearest_cdf_point(
+        &self,
+        cursor_pos: Point,
+        bounds: Rectangle,
+        state: &LineGraphState,
+    ) -> Option<usize> {
+        let plot = cdf_plot(bounds.size());
+        if cursor_pos.x < plot.x || cursor_pos.x > plot.x + plot.width {
+            return None;
+        }
+
+        // x never decreases with rank, so search instead of scanning every point
+        let sorted = self.cdf_sorted(state);
+        let extent = self.cdf_x_extent(&sorted)?;
+        let x = |rank: usize| self.cdf_x(sorted[rank], extent, plot);
+        let after = sorted.partition_point(|v| self.cdf_x(*v, extent, plot) < cursor_pos.x);
+        let nearest = match (
+            after.checked_sub(1),
+            (after < sorted.len()).then_some(after),
+        ) {
+            (Some(before), Some(after)) => {
+                if cursor_pos.x - x(before) <= x(after) - cursor_pos.x {
+                    before
+                } else {
+                    after
+                }
+            }
+            (Some(rank), None) | (None, Some(rank)) => rank,
+            (None, None) => return None,
+        };
+        // Equal values share an x; report the highest rank so the percentage is "≤"
+        let nearest_x = x(nearest);
+        Some(sorted.partition_point(|v| self.cdf_x(*v, extent, plot) <= nearest_x) - 1)
+    }
+
+    /// Draw the whole series as a cumulative distribution: sorted values along x,
+    /// 0–100% along y. Zoom and pan don't apply in this layout.
+    pub(super) fn draw_cdf(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        state: &LineGraphState,
+        theme: &Theme,
+    ) {
+        let palette = theme.extended_palette();
+        let plot = cdf_plot(bounds);
+        let (padding, chart_width, chart_height) = (plot.x, plot.width, plot.height);
+
+        let sorted = self.cdf_sorted(state);
+        let Some((min, max)) = self.cdf_x_extent(&sorted) else {
+            return;
+        };
+        let points: Vec<Point> = (0..sorted.len())
+            .map(|rank| self.cdf_point(&sorted, (min, max), rank, plot))
+            .collect();
+
+        if self.show_grid {
+            self.draw_grid(frame, padding, chart_width, chart_height, &[], palette);
+        }
+
+        // Step from one sample to the next so flat runs read as "no samples here"
+        let mut steps = Vec::with_capacity(points.len() * 2);
+        steps.push(Point::new(points[0].x, padding + chart_height));
+        for pair in points.windows(2) {
+            steps.push(pair[0]);
+            steps.push(Point::new(pair[1].x, pair[0].y));
+        }
+        steps.push(points[points.len() - 1]);
+        self.draw_line(frame, &steps, palette);
+
+        if self.show_labels {
+            let text_color = self.text_color(palette);
+
+            for i in 0..=4 {
+                let percent = i as f32 * 25.0;
+                frame.fill_text(canvas::Text {
+                    content: format!("{percent:.0}%"),
+                    position: Point::new(
+                        padding - 5.0,
+                        padding + chart_height * (1.0 - percent / 100.0),
+                    ),
+                    color: text_color,
+                    size: Pixels(9.0),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_x: Right.into(),
+                    align_y: Center.into(),
+                    ..canvas::Text::default()
+                });
+            }
+
+            for i in 0..=4 {
+                let fraction = i as f64 / 4.0;
+                frame.fill_text(canvas::Text {
+                    content: self.labels.format_y_axis(match self.x_scale {
+                        XScale::Linear => min + (max - min) * fraction,
+                        XScale::Log10 => min * (max / min).powf(fraction),
+                    }),
+                    position: Point::new(
+                        padding + chart_width * fraction as f32,
+                        padding + chart_height + 14.0,
+                    ),
+                    color: text_color.scale_alpha(0.7),
+                    size: Pixels(9.0),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_x: Center.into(),
+                    align_y: Bottom,
+                    ..canvas::Text::default()
+                });
+            }
+        }
+    }
+
+    /// Marker and "≤" tooltip for the hovered rank, drawn over the static layer
+    pub(super) fn draw_cdf_hover(
+        &self,
+        frame: &mut canvas::Frame,
+        bounds: Size,
+        state: &LineGraphState,
+        rank: usize,
+    ) {
+        let sorted = self.cdf_sorted(state);
+        if rank < sorted.len()
+            && let Some(extent) = self.cdf_x_extent(&sorted)
+        {
+            let point = self.cdf_point(&sorted, extent, rank, cdf_plot(bounds));
+            let marker_color = self.line_color.unwrap_or(Color::from_rgb(0.2, 0.6, 1.0));
+            frame.fill(
+                &canvas::Path::circle(point, self.point_radius + 2.0),
+                marker_color,
+            );
+            draw_tooltip(
+                frame,
+                &self.tooltip_style,
+                Point::new(point.x, point.y - self.point_radius - 8.0),
+                format!(
+                    "{:.1}% of samples ≤ {}",
+                    Self::cdf_percent(rank, sorted.len()),
+                    self.labels.format_tooltip(sorted[rank])
+                ),
+                11.0,
+                marker_color,
+                text_shaping(self.advanced_text),
+            );
+        }
+    }
+}
+
+/// Plot area of the CDF layout within `bounds`
+fn cdf_plot(bounds: Size) -> Rectangle {
+    Rectangle::new(
+        Point::new(CHART_PADDING, CHART_PADDING),
+        Size::new(
+            bounds.width - 2.0 * CHART_PADDING,
+            bounds.height - 2.0 * CHART_PADDING,
+        ),
+    )
+}
@@ -0,0 +1,231 @@
+//! Pure layout computation for line graphs, shared by drawing and hit testing
+
+use std::ops::Range;
+
+use super::overview::{OVERVIEW_GAP, OVERVIEW_HEIGHT};
+use super::{LineGraph, PointPlacement, XScale, state::LineGraphState};
+use crate::{
+    utils::{ValueMapper, mean_std_dev},
+    zoom::Zoom,
+};
+use iced::{Point, Rectangle, Size};
+
+/// Space around the plot area on every side, in pixels
+pub const CHART_PADDING: f32 = 40.0;
+
+/// Extra space above the plot for the title and subtitle, when they are shown
+pub const HEADER_HEIGHT: f32 = 24.0;
+
+/// Where each visible datapoint lands on a canvas of a given size
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineLayout {
+    /// Indices of the visible datapoints within the full series
+    pub window: Range<usize>,
+    /// Length of the full series
+    pub total: usize,
+    /// Zoom the window was computed for
+    pub zoom: Zoom,
+    /// Series index of each plotted datapoint; consecutive unless decimated
+    pub indices: Vec<usize>,
+    /// Mapped values of the plotted datapoints
+    pub values: Vec<f64>,
+    /// Canvas position of each visible datapoint
+    pub points: Vec<Point>,
+    /// Canvas positions of the [`LineGraph::baseline_series`] over the same window
+    pub baseline_points: Vec<Point>,
+    /// Canvas positions of the (lower, upper) [`LineGraph::bounds_fn`] band per datapoint
+    pub band: Vec<(Point, Point)>,
+    /// Plotted positions where the line restarts after a time gap longer than
+    /// [`LineGraph::max_gap`]
+    pub breaks: Vec<usize>,
+    pub min_value: f64,
+    pub max_value: f64,
+    pub average: f64,
+    /// Population standard deviation of the visible values
+    pub std_dev: f64,
+    pub padding: f32,
+    /// Top edge of the plot; equals `padding` unless letterboxed by an aspect ratio
+    pub top: f32,
+    /// Left edge of the plot; equals `padding` unless auto padding widened or narrowed it
+    pub left: f32,
+    pub chart_width: f32,
+    pub chart_height: f32,
+    /// The [`LineGraph::overview`] strip under the plot, if enabled
+    pub overview: Option<Rectangle>,
+}
+
+impl<'a, I, T, M> LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Compute the visible window and point positions for a canvas of `bounds`.
+    ///
+    /// This is what `draw` renders and what hover hit-tests against; it needs no
+    /// renderer, so it can be used to inspect or test the layout directly.
+    /// Returns `None` when there is nothing to plot.
+    pub fn compute_layout(&self, bounds: Size, state: &LineGraphState) -> Option<LineLayout> {
+        let total = self.len;
+        let zoom = self.effective_zoom(state);
+        let window = self.window_indices(total, state, self.visible_count(total, zoom));
+        if window.is_empty() {
+            return None;
+        }
+
+        let padding = CHART_PADDING;
+        let header = if self.show_labels && self.show_title {
+            HEADER_HEIGHT
+        } else {
+            0.0
+        };
+        let mut top = padding + header;
+        let mut chart_height = bounds.height - 2.0 * padding - header;
+
+        let visible = self
+            .datapoints
+            .clone()
+            .skip(window.start)
+            .take(window.len());
+        let (values, band): (Vec<f64>, Vec<(f64, f64)>) = match &self.band_fn {
+            Some(band_fn) => visible.map(|v| (self.mapper.map(&v), band_fn(&v))).unzip(),
+            None => (visible.map(|v| self.mapper.map(&v)).collect(), Vec::new()),
+        };
+        // The ghost series shares the axis, so it widens the range too
+        let baseline: &[f64] = match &self.baseline_series {
+            Some((series, _)) => {
+                &series[window.start.min(series.len())..window.end.min(series.len())]
+            }
+            None => &[],
+        };
+        // So do the band's extents
+        let extents: Vec<f64> = band.iter().flat_map(|&(low, high)| [low, high]).collect();
+        let mut min_value = values
+            .iter()
+            .chain(baseline)
+            .chain(&extents)
+            .fold(f64::INFINITY, |a, &b| a.min(b));
+        let mut max_value = values
+            .iter()
+            .chain(baseline)
+            .chain(&extents)
+            .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+        if let Some((low, high)) = self.stable_y {
+            min_value = min_value.min(low);
+            max_value = max_value.max(high);
+        }
+        if self.range_padding > 0.0 {
+            // A flat series has no range to scale, so pad by its magnitude and center it
+            let span = match max_value - min_value {
+                0.0 => max_value.abs().max(1.0),
+                span => span,
+            };
+            min_value -= span * self.range_padding;
+            max_value += span * self.range_padding;
+        }
+        let value_range = max_value - min_value;
+        let range_den: f64 = if value_range == 0.0 { 1.0 } else { value_range };
+        let (average, std_dev) = mean_std_dev(&values);
+
+        let mut left = if self.auto_padding && self.show_labels {
+            let y_ticks = self.y_tick_positions(top, chart_height, min_value, max_value);
+            self.auto_left_padding(&y_ticks)
+        } else {
+            padding
+        };
+        let right = if self.right_axis_labels && self.show_labels {
+            left.max(padding)
+        } else {
+            padding
+        };
+        let mut chart_width = bounds.width - left - right;
+
+        // Letterbox to the requested ratio, centered in the space available
+        if let Some(aspect) = self.aspect {
+            let x_span = values.len().saturating_sub(1).max(1) as f32;
+            let y_span = range_den as f32;
+            let scale = (chart_width / x_span).min(chart_height / (aspect * y_span));
+            let (width, height) = (x_span * scale, aspect * y_span * scale);
+            left += (chart_width - width) / 2.0;
+            top += (chart_height - height) / 2.0;
+            chart_width = width;
+            chart_height = height;
+        }
+
+        // Positions follow the index in the window, so decimated points keep their place
+        let count = values.len();
+        let (first, last) = ((window.start + 1) as f64, window.end as f64);
+        let place = |(i, value): (usize, &f64)| {
+            let fraction = match (self.x_scale, self.point_placement) {
+                (XScale::Log10, _) => XScale::Log10.fraction(first + i as f64, first, last),
+                (_, PointPlacement::EdgeToEdge) => i as f32 / count.saturating_sub(1).max(1) as f32,
+                (_, PointPlacement::Inset) => (i as f32 + 0.5) / count as f32,
+            };
+            let x = left + fraction * chart_width;
+            let normalized_value = (value - min_value) / range_den;
+            let y = top + chart_height - (normalized_value as f32 * chart_height);
+            Point::new(x, y)
+        };
+        let decimated = match zoom {
+            Zoom::Full => self.decimation.decimate(&values, chart_width as usize),
+            Zoom::Value(_) => None,
+        };
+        let (offsets, values): (Vec<usize>, Vec<f64>) = match decimated {
+            Some(kept) => kept.into_iter().unzip(),
+            None => ((0..count).collect(), values),
+        };
+        let points = offsets.iter().copied().zip(&values).map(place).collect();
+        let baseline_points = baseline.iter().enumerate().map(place).collect();
+        let band = offsets
+            .iter()
+            .filter_map(|&i| Some((i, band.get(i)?)))
+            .map(|(i, (low, high))| (place((i, low)), place((i, high))))
+            .collect();
+        let breaks = match &self.max_gap {
+            Some((gap, timestamp)) => {
+                let times: Vec<_> = self
+                    .datapoints
+                    .clone()
+                    .skip(window.start)
+                    .take(window.len())
+                    .map(|v| timestamp(&v))
+                    .collect();
+                let gap_before = |i: usize| times[i].saturating_sub(times[i - 1]) > *gap;
+                // Decimated points break if any gap falls between them
+                (1..offsets.len())
+                    .filter(|&k| (offsets[k - 1] + 1..=offsets[k]).any(gap_before))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        let indices = offsets.iter().map(|i| window.start + i).collect();
+        // Sits in the bottom padding, so the plot keeps its size
+        let overview = self.overview.then_some(Rectangle {
+            x: left,
+            y: top + chart_height + OVERVIEW_GAP,
+            width: chart_width,
+            height: OVERVIEW_HEIGHT,
+        });
+
+        Some(LineLayout {
+            window,
+            total,
+            zoom,
+            indices,
+            values,
+            points,
+            baseline_points,
+            band,
+            breaks,
+            min_value,
+            max_value,
+            average,
+            std_dev,
+            padding,
+            top,
+            left,
+            chart_width,
+            chart_height,
+            overview,
+        })
+    }
+}
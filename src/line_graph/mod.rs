@@ -1,11 +1,20 @@
 pub use canvas::Cache;
+
+use std::{cell::Cell, time::Duration};
+
 use iced::{
-    Center, Color, Event, Font, Pixels, Point, Rectangle, Renderer, Right, Size, Theme, keyboard,
-    mouse, widget::canvas,
+    Bottom, Center, Color, Event, Font, Pixels, Point, Rectangle, Renderer, Right, Size, Theme,
+    Top, Vector, keyboard, keyboard::key, mouse, time::Instant, widget::canvas,
 };
 
 use crate::{
-    utils::{DefaultMap, GridConfig, LabelFormatter, ValueMapper, draw_grid},
+    utils::{
+        Corner, DashPattern, DefaultMap, GridAxes, GridConfig, GridLayer, LabelFormatter,
+        MEDIAN_COLOR, MEDIAN_DASH, ShadowConfig, TooltipStyle, ValueMapper, clamp_reference_line,
+        draw_dashed_average, draw_grid, draw_loading_overlay, draw_tooltip, draw_watermark,
+        estimate_text_width, format_deviation, format_value, median, nice_ticks, text_shaping,
+        tooltip_size,
+    },
     zoom::Zoom,
 };
 
@@ -14,7 +23,53 @@ pub mod color_scheme;
 use color_scheme::{PointColorParams, PointColorScheme};
 
 pub mod state;
-use state::{LineGraphState, PanMode};
+use state::{LineGraphState, PanMode, SharedPlot, SharedView};
+
+mod cdf;
+mod overview;
+
+pub mod layout;
+pub use layout::LineLayout;
+
+/// Pixel scroll deltas (trackpads) per wheel line when zooming
+const PIXELS_PER_LINE: f32 = 40.0;
+
+/// Font size of the Y-axis labels
+const Y_LABEL_SIZE: f32 = 9.0;
+
+/// Space between the Y-axis labels' backdrop and the plot, beyond the text itself
+const LABEL_GUTTER: f32 = 6.0;
+
+/// Returns the (lower, upper) extents of the shaded band around a datapoint
+pub type BandFn<T> = Box<dyn Fn(&T) -> (f64, f64) + Send + Sync>;
+
+/// Picks a health tint for the whole chart from the latest visible value
+pub type StatusFn = Box<dyn Fn(f64) -> Option<Color> + Send + Sync>;
+
+/// Receives the visible (original index, value) pairs whenever the plot is redrawn
+pub type VisibleFn = Box<dyn Fn(&[(usize, f64)]) + Send + Sync>;
+
+/// Radius of a point marker from its value and the visible (min, max) range
+pub type PointRadiusFn = Box<dyn Fn(f64, f64, f64) -> f32 + Send + Sync>;
+
+/// Largest radius a [`PointRadiusFn`] may give a point marker
+const MAX_POINT_RADIUS: f32 = 30.0;
+
+/// How long the visible window must stay unchanged before [`LineGraph::report_window`]
+/// publishes it
+const WINDOW_SETTLE: Duration = Duration::from_millis(200);
+
+/// Most appended points animated at once by [`LineGraph::point_entry_animation`]
+const MAX_POINT_ENTRIES: usize = 256;
+
+/// Even divisions of the plot height drawn as horizontal gridlines
+const GRID_LINES: usize = 10;
+
+/// Reads the timestamp of a datapoint, e.g. since the start of a recording
+pub type TimeFn<T> = Box<dyn Fn(&T) -> Duration + Send + Sync>;
+
+/// Picks the line color from whether the theme is dark and its palette
+pub type ThemeColorFn = Box<dyn Fn(bool, &iced::theme::palette::Extended) -> Color + Send + Sync>;
 
 // Re-export the shared interaction type for backward compatibility
 pub use crate::utils::LineInteraction as Interaction;
@@ -27,11 +82,61 @@ where
     pub datapoints: I,
     pub cache: &'a canvas::Cache,
     pub line_color: Option<Color>,
+    pub theme_color_fn: Option<ThemeColorFn>,
     pub line_width: f32,
+    pub line_cap: canvas::LineCap,
+    pub line_join: canvas::LineJoin,
+    pub baseline_series: Option<(Vec<f64>, Color)>,
+    pub band_fn: Option<BandFn<T>>,
+    pub max_gap: Option<(Duration, TimeFn<T>)>,
     pub show_points: bool,
+    pub mark_extrema: bool,
+    pub as_columns: bool,
+    pub zero_style: ZeroStyle,
+    pub point_placement: PointPlacement,
+    pub x_scale: XScale,
+    pub decimation: DecimationMode,
     pub point_radius: f32,
+    pub point_radius_fn: Option<PointRadiusFn>,
+    pub min_point_gap: f32,
+    pub collapse_flats: bool,
     pub show_grid: bool,
+    pub value_bands: Vec<(f64, f64, Color)>,
+    pub highlight_above: Option<(f64, Color)>,
+    pub grid_axes: GridAxes,
+    pub grid_layer: GridLayer,
+    pub grid_color: Option<Color>,
+    pub minor_subdivisions: usize,
+    pub grid_fade: f32,
     pub show_labels: bool,
+    pub show_title: bool,
+    pub label_color: Option<Color>,
+    pub average_color: Option<Color>,
+    pub average_label_color: Option<Color>,
+    pub auto_padding: bool,
+    pub right_axis_labels: bool,
+    pub aspect: Option<f32>,
+    pub range_padding: f64,
+    pub stable_y: Option<(f64, f64)>,
+    pub show_data_info: bool,
+    pub loading: bool,
+    pub clear_background: bool,
+    pub background_color: Option<Color>,
+    pub watermark: Option<(String, Corner, f32)>,
+    pub advanced_text: bool,
+    pub status_fn: Option<StatusFn>,
+    pub on_visible: Option<VisibleFn>,
+    pub live: bool,
+    pub point_entry: Option<Duration>,
+    pub label_endpoints: bool,
+    pub overview: bool,
+    pub show_median: bool,
+    pub sticky_hover: bool,
+    pub hover_show_deviation: bool,
+    pub hover_mode: HoverMode,
+    pub pin_on_click: bool,
+    pub clamp_average: bool,
+    pub average_band: bool,
     pub zoom: Zoom,
     pub base_points: f32,
     pub zoom_min: f32,
@@ -39,9 +144,26 @@ where
     pub point_color_scheme: PointColorScheme,
     pub mapper: M,
     pub external_zoom: Option<Zoom>, // Optional external zoom override
+    pub shared_view: Option<SharedView>,
     pub len: usize,
     pub labels: LabelConfig,
     pub zoom_anchor: ZoomAnchor,
+    pub anchor: Anchor,
+    pub tooltip_style: TooltipStyle,
+    pub shadow: ShadowConfig,
+    pub average_dash: DashPattern,
+    pub average_glow: f32,
+    pub y_ticks: Option<Vec<f64>>,
+    pub nice_ticks: bool,
+    pub align_labels_to_grid: bool,
+    pub zoom_enabled: bool,
+    pub scroll_passthrough: bool,
+    pub scroll_behavior: ScrollBehavior,
+    pub zoom_step: f32,
+    pub integer_zoom: bool,
+    pub cdf: bool,
+    pub capture_events: bool,
+    pub report_window: bool,
 }
 
 impl<'a, I, T> LineGraph<'a, I, T>
@@ -55,11 +177,61 @@ where
             datapoints,
             cache,
             line_color: None,
+            theme_color_fn: None,
             line_width: 2.0,
+            line_cap: canvas::LineCap::Butt,
+            line_join: canvas::LineJoin::Miter,
+            baseline_series: None,
+            band_fn: None,
+            max_gap: None,
             show_points: true,
+            mark_extrema: false,
+            as_columns: false,
+            zero_style: ZeroStyle::Plain,
+            point_placement: PointPlacement::EdgeToEdge,
+            x_scale: XScale::Linear,
+            decimation: DecimationMode::None,
             point_radius: 3.0,
+            point_radius_fn: None,
+            min_point_gap: 0.0,
+            collapse_flats: false,
             show_grid: true,
+            value_bands: Vec::new(),
+            highlight_above: None,
+            grid_axes: GridAxes::Both,
+            grid_layer: GridLayer::Behind,
+            grid_color: None,
+            minor_subdivisions: 0,
+            grid_fade: 0.0,
             show_labels: true,
+            show_title: true,
+            label_color: None,
+            average_color: None,
+            average_label_color: None,
+            auto_padding: false,
+            right_axis_labels: false,
+            aspect: None,
+            range_padding: 0.0,
+            stable_y: None,
+            show_data_info: false,
+            loading: false,
+            clear_background: true,
+            background_color: None,
+            watermark: None,
+            advanced_text: false,
+            status_fn: None,
+            on_visible: None,
+            live: false,
+            point_entry: None,
+            label_endpoints: false,
+            overview: false,
+            show_median: false,
+            sticky_hover: false,
+            hover_show_deviation: false,
+            hover_mode: HoverMode::Nearest,
+            pin_on_click: false,
+            clamp_average: true,
+            average_band: false,
             zoom: Zoom::default(),
             base_points: 50.0, // Increased default from 20.0
             zoom_min: 0.1,
@@ -67,12 +239,55 @@ where
             point_color_scheme: PointColorScheme::default(),
             mapper: DefaultMap,
             external_zoom: None,
+            shared_view: None,
             labels: LabelConfig::default(),
             zoom_anchor: ZoomAnchor::End,
+            anchor: Anchor::End,
+            tooltip_style: TooltipStyle::default(),
+            shadow: ShadowConfig::default(),
+            average_dash: DashPattern::default(),
+            average_glow: 1.0,
+            y_ticks: None,
+            nice_ticks: true,
+            align_labels_to_grid: false,
+            zoom_enabled: true,
+            scroll_passthrough: false,
+            scroll_behavior: ScrollBehavior::Zoom,
+            zoom_step: 1.0,
+            integer_zoom: false,
+            cdf: false,
+            capture_events: true,
+            report_window: false,
         }
     }
 }
 
+/// A line graph that owns its data, so only the cache needs to outlive it
+pub type OwnedLineGraph<'a, T> = LineGraph<'a, std::vec::IntoIter<T>, T>;
+
+impl<'a, T> OwnedLineGraph<'a, T>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    /// Construct from owned data, e.g. values derived inside `view`.
+    ///
+    /// The data is copied each time the graph iterates it; prefer
+    /// [`LineGraph::from_slice`] when the data already lives in app state.
+    pub fn from_vec(data: Vec<T>, cache: &'a canvas::Cache) -> Self {
+        Self::new(data.into_iter(), cache)
+    }
+}
+
+impl<'a, T> LineGraph<'a, std::iter::Copied<std::slice::Iter<'a, T>>, T>
+where
+    T: Copy + Into<f64> + 'a,
+{
+    /// Construct from a slice without spelling out `.iter().copied()`
+    pub fn from_slice(data: &'a [T], cache: &'a canvas::Cache) -> Self {
+        Self::new(data.iter().copied(), cache)
+    }
+}
+
 impl<'a, I, T, M> LineGraph<'a, I, T, M>
 where
     I: Iterator<Item = T> + Clone + 'a,
@@ -109,12 +324,20 @@ where
             return 0..0;
         }
 
+        // Until the pan is first set, the configured anchor decides where to open
+        let initial = self.anchor.pan_mode();
+        let pan_mode = if state.pan_seeded {
+            &state.pan.mode
+        } else {
+            &initial
+        };
+
         let start = if total > max_visible_points {
             let max_start = total.saturating_sub(max_visible_points);
-            match state.pan.mode {
+            match pan_mode {
                 PanMode::Start => 0,
                 PanMode::End => max_start,
-                PanMode::Absolute(s) => s.min(max_start),
+                PanMode::Absolute(s) => (*s).min(max_start),
             }
         } else {
             0
@@ -133,11 +356,61 @@ where
             datapoints,
             cache,
             line_color: None,
+            theme_color_fn: None,
             line_width: 2.0,
+            line_cap: canvas::LineCap::Butt,
+            line_join: canvas::LineJoin::Miter,
+            baseline_series: None,
+            band_fn: None,
+            max_gap: None,
             show_points: true,
+            mark_extrema: false,
+            as_columns: false,
+            zero_style: ZeroStyle::Plain,
+            point_placement: PointPlacement::EdgeToEdge,
+            x_scale: XScale::Linear,
+            decimation: DecimationMode::None,
             point_radius: 3.0,
+            point_radius_fn: None,
+            min_point_gap: 0.0,
+            collapse_flats: false,
             show_grid: true,
+            value_bands: Vec::new(),
+            highlight_above: None,
+            grid_axes: GridAxes::Both,
+            grid_layer: GridLayer::Behind,
+            grid_color: None,
+            minor_subdivisions: 0,
+            grid_fade: 0.0,
             show_labels: true,
+            show_title: true,
+            label_color: None,
+            average_color: None,
+            average_label_color: None,
+            auto_padding: false,
+            right_axis_labels: false,
+            aspect: None,
+            range_padding: 0.0,
+            stable_y: None,
+            show_data_info: false,
+            loading: false,
+            clear_background: true,
+            background_color: None,
+            watermark: None,
+            advanced_text: false,
+            status_fn: None,
+            on_visible: None,
+            live: false,
+            point_entry: None,
+            label_endpoints: false,
+            overview: false,
+            show_median: false,
+            sticky_hover: false,
+            hover_show_deviation: false,
+            hover_mode: HoverMode::Nearest,
+            pin_on_click: false,
+            clamp_average: true,
+            average_band: false,
             zoom: Zoom::default(),
             base_points: 50.0,
             zoom_min: 0.1,
@@ -145,8 +418,25 @@ where
             point_color_scheme: PointColorScheme::default(),
             mapper,
             external_zoom: None,
+            shared_view: None,
             labels: LabelConfig::default(),
             zoom_anchor: ZoomAnchor::End,
+            anchor: Anchor::End,
+            tooltip_style: TooltipStyle::default(),
+            shadow: ShadowConfig::default(),
+            average_dash: DashPattern::default(),
+            average_glow: 1.0,
+            y_ticks: None,
+            nice_ticks: true,
+            align_labels_to_grid: false,
+            zoom_enabled: true,
+            scroll_passthrough: false,
+            scroll_behavior: ScrollBehavior::Zoom,
+            zoom_step: 1.0,
+            integer_zoom: false,
+            cdf: false,
+            capture_events: true,
+            report_window: false,
         }
     }
 
@@ -162,6 +452,76 @@ where
         self
     }
 
+    /// Dash the average line with this pattern, or [`DashPattern::SOLID`] (default 15/5)
+    pub fn average_dash(mut self, dash: DashPattern) -> Self {
+        self.average_dash = dash;
+        self
+    }
+
+    /// Scale the glow behind the average and median lines: 1.0 is the default,
+    /// 0.0 disables it, e.g. for small charts where it looks heavy
+    pub fn average_glow(mut self, intensity: f32) -> Self {
+        self.average_glow = intensity.max(0.0);
+        self
+    }
+
+    /// Zoom and pan together with every other graph given the same [`SharedView`]
+    pub fn shared_view(mut self, view: &SharedView) -> Self {
+        self.shared_view = Some(view.clone());
+        self
+    }
+
+    /// Respond to the scroll wheel (default true). When disabled, scroll events pass
+    /// through untouched so a parent scrollable can handle them.
+    pub fn zoom_enabled(mut self, enabled: bool) -> Self {
+        self.zoom_enabled = enabled;
+        self
+    }
+
+    /// Let scroll bubble to a parent scrollable once zoom or pan hits a limit,
+    /// instead of capturing every wheel event over the graph (default false)
+    pub fn scroll_passthrough(mut self, passthrough: bool) -> Self {
+        self.scroll_passthrough = passthrough;
+        self
+    }
+
+    /// What the scroll wheel does over the graph (default [`ScrollBehavior::Zoom`])
+    pub fn scroll_behavior(mut self, behavior: ScrollBehavior) -> Self {
+        self.scroll_behavior = behavior;
+        self
+    }
+
+    /// Publish [`Interaction::WindowChanged`] once the visible window has stayed the
+    /// same for a moment after zooming or panning, so a host can load just that
+    /// slice without reacting to every scroll tick (default false)
+    pub fn report_window(mut self, report: bool) -> Self {
+        self.report_window = report;
+        self
+    }
+
+    /// Capture the events the graph handles (scroll zoom/pan, arrow keys) so they stop
+    /// propagating (default true). When false, interactions are still published but
+    /// the event continues on to the rest of the app.
+    pub fn capture_events(mut self, capture: bool) -> Self {
+        self.capture_events = capture;
+        self
+    }
+
+    /// Fraction of a zoom increment applied per wheel line (default 1.0, one
+    /// increment per notch). Lower it to smooth out trackpads, which emit many
+    /// small pixel deltas per gesture.
+    pub fn zoom_step(mut self, step: f32) -> Self {
+        self.zoom_step = step.clamp(0.01, 1.0);
+        self
+    }
+
+    /// Only zoom to whole levels (1x, 2x, 3x, ...) and full view, skipping the
+    /// fractional levels below 1x; `zoom_min` no longer applies
+    pub fn integer_zoom(mut self, integer: bool) -> Self {
+        self.integer_zoom = integer;
+        self
+    }
+
     // Label configuration
     pub fn label_config(mut self, labels: LabelConfig) -> Self {
         self.labels = labels;
@@ -188,212 +548,1407 @@ where
         self
     }
 
+    /// Format values as durations, choosing µs, ms or s by magnitude (input in ms)
+    pub fn auto_time_unit(mut self, auto: bool) -> Self {
+        self.labels.auto_time_unit = auto;
+        self
+    }
+
+    /// Group the integer digits of every label, e.g. `','` for "12,345"
+    pub fn thousands_separator(mut self, separator: char) -> Self {
+        self.labels.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Label whole-number data such as request or error counts: no decimals and
+    /// no time scaling, still with the unit suffix
+    pub fn integer_values(mut self, integer: bool) -> Self {
+        self.labels.integer_values = integer;
+        self
+    }
+
     pub fn title_text(mut self, title: Option<String>) -> Self {
         self.labels.title = title;
         self
     }
 
+    /// Draw Y-axis gridlines and labels at exactly these values instead of even divisions.
+    /// Ticks outside the visible value range are skipped.
+    pub fn y_ticks(mut self, ticks: Vec<f64>) -> Self {
+        self.y_ticks = Some(ticks);
+        self
+    }
+
+    /// Place automatic Y-axis ticks on round values (default). When disabled,
+    /// the value range is split into five even divisions instead.
+    pub fn nice_ticks(mut self, nice: bool) -> Self {
+        self.nice_ticks = nice;
+        self
+    }
+
+    /// Label every horizontal gridline instead of using separate label steps, so
+    /// each label sits exactly on a line. Explicit [`y_ticks`](Self::y_ticks) still
+    /// take precedence.
+    pub fn align_labels_to_grid(mut self, align: bool) -> Self {
+        self.align_labels_to_grid = align;
+        self
+    }
+
     pub fn zoom_anchor(mut self, anchor: ZoomAnchor) -> Self {
         self.zoom_anchor = anchor;
         self
     }
 
+    /// Open on the start (oldest) or end (most recent, default) of the series.
+    /// Only seeds the initial pan; scrolling or setting the pan on the state takes over.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Streaming mode: open on the most recent data, mark the latest sample with
+    /// a leading edge line and show a "LIVE" badge, dimmed while panned away
+    pub fn live(mut self, live: bool) -> Self {
+        self.live = live;
+        if live {
+            self.anchor = Anchor::End;
+        }
+        self
+    }
+
+    /// Grow the markers of newly appended points in over `duration`, so a live
+    /// chart's new samples stand out as they arrive. The initial series appears as is.
+    pub fn point_entry_animation(mut self, duration: Duration) -> Self {
+        self.point_entry = Some(duration);
+        self
+    }
+
+    /// Label the first and last visible values next to their points
+    pub fn label_endpoints(mut self, label: bool) -> Self {
+        self.label_endpoints = label;
+        self
+    }
+
+    /// Draw the whole series in a thin strip under the plot with the visible window
+    /// marked. Drag the window to pan, or its edges to zoom.
+    pub fn overview(mut self, overview: bool) -> Self {
+        self.overview = overview;
+        self
+    }
+
+    pub fn tooltip_style(mut self, style: TooltipStyle) -> Self {
+        self.tooltip_style = style;
+        self
+    }
+
+    /// Configure (or disable) the drop shadow behind the line and points
+    pub fn shadow(mut self, shadow: ShadowConfig) -> Self {
+        self.shadow = shadow;
+        self
+    }
+
     pub fn line_color(mut self, color: Color) -> Self {
         self.line_color = Some(color);
         self
     }
 
+    /// Choose the line color per theme, e.g. a different brand accent for light
+    /// and dark; called with `is_dark` and the palette at draw time. `line_color` wins if set.
+    pub fn theme_color_fn<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(bool, &iced::theme::palette::Extended) -> Color + Send + Sync + 'static,
+    {
+        self.theme_color_fn = Some(Box::new(color_fn));
+        self
+    }
+
+    /// The line color: the override, else the theme function, else blue
+    fn base_line_color(&self, palette: &iced::theme::palette::Extended) -> Color {
+        self.line_color
+            .or_else(|| {
+                self.theme_color_fn
+                    .as_ref()
+                    .map(|f| f(palette.is_dark, palette))
+            })
+            .unwrap_or(Color::from_rgb(0.2, 0.6, 1.0))
+    }
+
     pub fn line_width(mut self, width: f32) -> Self {
         self.line_width = width;
         self
     }
 
+    /// Shape of the line's ends, e.g. [`canvas::LineCap::Round`] (default butt)
+    pub fn line_cap(mut self, cap: canvas::LineCap) -> Self {
+        self.line_cap = cap;
+        self
+    }
+
+    /// Shape of the line's corners; [`canvas::LineJoin::Round`] smooths curvy data (default miter)
+    pub fn line_join(mut self, join: canvas::LineJoin) -> Self {
+        self.line_join = join;
+        self
+    }
+
     pub fn show_points(mut self, show: bool) -> Self {
         self.show_points = show;
         self
     }
 
-    pub fn point_radius(mut self, radius: f32) -> Self {
-        self.point_radius = radius;
+    /// Clicking a point pins its tooltip open so several can be compared; click
+    /// it again to unpin. Publishes `PointPinned`/`PointUnpinned` instead of `PointClicked`.
+    pub fn pin_on_click(mut self, pin: bool) -> Self {
+        self.pin_on_click = pin;
         self
     }
 
-    pub fn show_grid(mut self, show: bool) -> Self {
-        self.show_grid = show;
+    /// Draw points only at local minima and maxima of the visible values, at any
+    /// zoom, labelled with their value when labels are shown
+    pub fn mark_extrema(mut self, mark: bool) -> Self {
+        self.mark_extrema = mark;
         self
     }
 
-    pub fn show_labels(mut self, show: bool) -> Self {
-        self.show_labels = show;
+    /// Shade a band between per-point (lower, upper) extents around the line,
+    /// e.g. a confidence interval or forecast range. The band widens the value range.
+    pub fn bounds_fn<F>(mut self, bounds_fn: F) -> Self
+    where
+        F: Fn(&T) -> (f64, f64) + Send + Sync + 'static,
+    {
+        self.band_fn = Some(Box::new(bounds_fn));
         self
     }
 
-    pub fn base_points(mut self, points: f32) -> Self {
-        self.base_points = points;
+    /// Break the line where consecutive samples are more than `gap` apart, reading
+    /// each sample's time with `timestamp`, so missing stretches of irregularly
+    /// sampled data aren't bridged
+    pub fn max_gap<F>(mut self, gap: Duration, timestamp: F) -> Self
+    where
+        F: Fn(&T) -> Duration + Send + Sync + 'static,
+    {
+        self.max_gap = Some((gap, Box::new(timestamp)));
         self
     }
 
-    pub fn zoom_range(mut self, min: f32, max: f32) -> Self {
-        self.zoom_min = min;
-        self.zoom_max = max;
+    /// Draw a second series faintly behind the line, e.g. last week under this week.
+    /// It shares the line's axis and window but has no points or hover.
+    pub fn baseline_series<V: Into<f64>>(
+        mut self,
+        series: impl IntoIterator<Item = V>,
+        color: Color,
+    ) -> Self {
+        self.baseline_series = Some((series.into_iter().map(Into::into).collect(), color));
         self
     }
 
-    pub fn zoom_min(mut self, min: f32) -> Self {
-        self.zoom_min = min;
+    /// Draw a thin column from the baseline up to each point instead of
+    /// connecting them, for discrete values where a line implies continuity
+    pub fn as_columns(mut self, columns: bool) -> Self {
+        self.as_columns = columns;
         self
     }
 
-    pub fn zoom_max(mut self, max: f32) -> Self {
-        self.zoom_max = max;
+    pub fn point_radius(mut self, radius: f32) -> Self {
+        self.point_radius = radius;
         self
     }
 
-    pub fn point_color_fn<F>(mut self, color_fn: F) -> Self
+    /// Size each point marker from `(value, min, max)` of the visible data, for a
+    /// bubble-plot look. Radii are clamped to 0.5..=30 pixels; hover rings grow
+    /// with them.
+    pub fn point_radius_fn<F>(mut self, radius_fn: F) -> Self
     where
-        F: Fn(&PointColorParams) -> Color + Send + Sync + 'static,
+        F: Fn(f64, f64, f64) -> f32 + Send + Sync + 'static,
     {
-        self.point_color_scheme = PointColorScheme::new_function(color_fn);
+        self.point_radius_fn = Some(Box::new(radius_fn));
         self
     }
 
-    pub fn single_point_color(mut self, color: Color) -> Self {
-        self.point_color_scheme = PointColorScheme::new_single(color);
-        self
+    /// Marker radius for `value`: from [`Self::point_radius_fn`] when set, else fixed
+    fn radius_for(&self, value: f64, min_value: f64, max_value: f64) -> f32 {
+        match &self.point_radius_fn {
+            Some(radius_fn) => {
+                let radius = radius_fn(value, min_value, max_value);
+                if radius.is_finite() {
+                    radius.clamp(0.5, MAX_POINT_RADIUS)
+                } else {
+                    self.point_radius
+                }
+            }
+            None => self.point_radius,
+        }
     }
 
-    pub fn point_color_scheme(mut self, scheme: PointColorScheme) -> Self {
-        self.point_color_scheme = scheme;
+    /// Skip a point marker closer than this many pixels to the last one drawn, so
+    /// dense lines don't blur into a blob (default 0, every marker). The line and
+    /// hover still use every point.
+    pub fn min_point_gap(mut self, gap: f32) -> Self {
+        self.min_point_gap = gap;
         self
     }
 
-    /// Get the effective zoom value (external zoom if set, otherwise state zoom)
-    fn effective_zoom(&self, state: &LineGraphState) -> Zoom {
-        self.external_zoom.unwrap_or(state.zoom)
+    /// Draw each run of identical consecutive values as one segment with a marker
+    /// at either end, and report the run's index range when hovering it
+    pub fn collapse_flats(mut self, collapse: bool) -> Self {
+        self.collapse_flats = collapse;
+        self
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct LabelConfig {
-    pub unit_suffix: String,
-    pub y_axis_decimals: u8,
-    pub tooltip_decimals: u8,
-    pub average_decimals: u8,
-    pub title: Option<String>,
-}
+    /// Mark zero values distinctly, so "no data" stands out from the rest of the line
+    pub fn zero_style(mut self, style: ZeroStyle) -> Self {
+        self.zero_style = style;
+        self
+    }
 
-impl Default for LabelConfig {
-    fn default() -> Self {
-        Self {
-            unit_suffix: "".to_string(),
-            y_axis_decimals: 0,
-            tooltip_decimals: 2,
-            average_decimals: 1,
-            title: None,
-        }
+    /// Where points sit horizontally; [`PointPlacement::Inset`] centers them in
+    /// equal slots, e.g. to line up with bars drawn underneath
+    pub fn point_placement(mut self, placement: PointPlacement) -> Self {
+        self.point_placement = placement;
+        self
     }
-}
 
-#[derive(Debug, Clone, Copy)]
-pub enum ZoomAnchor {
-    Start,
-    Middle,
-    End,
-}
+    /// Scale of the X axis: sample index + 1, or the value axis in CDF mode.
+    /// A logarithmic scale spreads out the start of a rank or latency plot.
+    pub fn x_scale(mut self, scale: XScale) -> Self {
+        self.x_scale = scale;
+        self
+    }
 
-impl crate::utils::LabelFormatter for LabelConfig {
-    fn format_y_axis(&self, value: f64) -> String {
-        format!(
-            "{v:.prec$}{unit}",
-            v = value,
-            prec = self.y_axis_decimals as usize,
-            unit = self.unit_suffix
-        )
+    /// Thin the full view to about one point (or a min/max pair) per pixel column,
+    /// for huge series. Zoomed-in views always draw every point.
+    pub fn decimation(mut self, mode: DecimationMode) -> Self {
+        self.decimation = mode;
+        self
     }
 
-    fn format_tooltip(&self, value: f64) -> String {
-        format!(
-            "{v:.prec$}{unit}",
-            v = value,
-            prec = self.tooltip_decimals as usize,
-            unit = self.unit_suffix
-        )
+    pub fn show_grid(mut self, show: bool) -> Self {
+        self.show_grid = show;
+        self
     }
 
-    fn format_average_text(&self, value: f64) -> String {
-        format!(
-            "avg: {v:.prec$}{unit}",
-            v = value,
-            prec = self.average_decimals as usize,
-            unit = self.unit_suffix
-        )
+    /// Shade `(from, to, color)` value ranges behind the line
+    pub fn value_bands(mut self, bands: Vec<(f64, f64, Color)>) -> Self {
+        self.value_bands = bands;
+        self
     }
 
-    fn format_title(&self, zoom: Zoom) -> Option<String> {
-        let title = self.title.as_ref()?;
-        let text = match zoom {
-            Zoom::Full => format!("{} (Full View)", title),
-            Zoom::Value(zoom_factor) => {
-                if zoom_factor.fract() == 0.0 {
-                    format!("{} (Zoom: {}x)", title, zoom_factor as u32)
-                } else {
-                    format!("{} (Zoom: {:.2}x)", title, zoom_factor)
-                }
-            }
-        };
-        Some(text)
+    /// Color points above `threshold` with `color`
+    pub fn highlight_above(mut self, threshold: f64, color: Color) -> Self {
+        self.highlight_above = Some((threshold, color));
+        self
     }
 
-    fn format_subtitle(
-        &self,
-        zoom: Zoom,
-        start_idx: usize,
-        end_idx: usize,
-        count: usize,
-    ) -> String {
-        match zoom {
-            Zoom::Full => format!(
-                "(Showing all {} points: x {}..{})",
-                count, start_idx, end_idx
-            ),
-            Zoom::Value(_) => format!("(Showing {} points: x {}..{})", count, start_idx, end_idx),
+    /// `color`, or the [`highlight_above`](Self::highlight_above) color if `value` exceeds its threshold
+    fn highlighted(&self, value: f64, color: Color) -> Color {
+        match self.highlight_above {
+            Some((threshold, highlight)) if value > threshold => highlight,
+            _ => color,
         }
     }
-}
 
-impl<'a, I, T, M> canvas::Program<Interaction> for LineGraph<'a, I, T, M>
-where
-    I: Iterator<Item = T> + Clone + 'a,
-    M: ValueMapper<T>,
-{
-    type State = LineGraphState;
+    /// Draw only horizontal or only vertical grid lines (default both)
+    pub fn grid_axes(mut self, axes: GridAxes) -> Self {
+        self.grid_axes = axes;
+        self
+    }
 
-    fn update(
-        &self,
-        state: &mut Self::State,
-        event: &Event,
-        bounds: Rectangle,
-        cursor: mouse::Cursor,
-    ) -> Option<canvas::Action<Interaction>> {
-        match event {
-            Event::Keyboard(keyboard::Event::ModifiersChanged(mods)) => {
-                let new_shift = mods.shift();
-                if state.shift_down != new_shift {
-                    state.shift_down = new_shift;
-                    return Some(canvas::Action::request_redraw());
-                }
-                None
-            }
-            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
-                // Only enable hover when zoomed in (not in full view) and points are visible
-                let effective_zoom = self.effective_zoom(state);
-                if effective_zoom.is_value() {
-                    if let Some(cursor_position) = cursor.position_in(bounds) {
-                        let new_hovered = self.find_nearest_point(cursor_position, bounds, state);
+    /// Draw the grid over the line and points instead of behind them (default behind)
+    pub fn grid_layer(mut self, layer: GridLayer) -> Self {
+        self.grid_layer = layer;
+        self
+    }
 
-                        if state.hovered_point != new_hovered {
-                            state.hovered_point = new_hovered;
-                            self.cache.clear();
+    /// Color for the grid lines (default: the theme's text color)
+    pub fn grid_color(mut self, color: Color) -> Self {
+        self.grid_color = Some(color);
+        self
+    }
+
+    /// Fainter lines splitting each grid cell `subdivisions + 1` ways (default 0)
+    pub fn minor_subdivisions(mut self, subdivisions: usize) -> Self {
+        self.minor_subdivisions = subdivisions;
+        self
+    }
+
+    /// Fade the grid behind the data by overlaying the chart background at this opacity (0.0 = off)
+    pub fn grid_fade(mut self, fade: f32) -> Self {
+        self.grid_fade = fade.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn show_labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
+        self
+    }
+
+    /// Draw the title and range subtitle above the plot (default), which reserves
+    /// space for them at the top; turn off to keep the axis labels without the header
+    pub fn show_title(mut self, show: bool) -> Self {
+        self.show_title = show;
+        self
+    }
+
+    /// Color for axis labels, titles and readouts, overriding the theme's text color
+    pub fn label_color(mut self, color: Color) -> Self {
+        self.label_color = Some(color);
+        self
+    }
+
+    /// Color of the average line and its marker, instead of the default orange
+    pub fn average_color(mut self, color: Color) -> Self {
+        self.average_color = Some(color);
+        self
+    }
+
+    /// Color of the average label text, independent of the line; defaults to white
+    pub fn average_label_color(mut self, color: Color) -> Self {
+        self.average_label_color = Some(color);
+        self
+    }
+
+    /// Widen the auto-scaled value range by `fraction` of it on each side (e.g. 0.05),
+    /// so the extremes and their markers don't hug the plot edges
+    pub fn range_padding(mut self, fraction: f64) -> Self {
+        self.range_padding = fraction.max(0.0);
+        self
+    }
+
+    /// Keep a fixed ratio of pixels per Y unit to pixels per X step (one datapoint),
+    /// letterboxing the plot centered within the bounds instead of stretching it.
+    /// `None` (default) fills the bounds.
+    pub fn aspect(mut self, aspect: Option<f32>) -> Self {
+        self.aspect = aspect.filter(|a| a.is_finite() && *a > 0.0);
+        self
+    }
+
+    /// Size the left padding to the widest Y-axis label instead of the fixed
+    /// padding, so large values don't overlap the plot and small ones don't waste space
+    pub fn auto_padding(mut self, auto: bool) -> Self {
+        self.auto_padding = auto;
+        self
+    }
+
+    /// Mirror the Y-axis labels on the right edge, reserving as much right padding
+    /// as the left labels take
+    pub fn right_axis_labels(mut self, show: bool) -> Self {
+        self.right_axis_labels = show;
+        self
+    }
+
+    /// Show a "showing N of M" readout of visible versus total datapoints
+    pub fn show_data_info(mut self, show: bool) -> Self {
+        self.show_data_info = show;
+        self
+    }
+
+    /// Dim the graph under a "Loading…" message and ignore input while data is fetched
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Fill the canvas with the background color before drawing (default on)
+    pub fn clear_background(mut self, clear: bool) -> Self {
+        self.clear_background = clear;
+        self
+    }
+
+    /// Color used by [`clear_background`](Self::clear_background), instead of the theme background
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    /// Faint `text` in a `corner` of the canvas at `opacity` of the label color
+    pub fn watermark(mut self, text: impl Into<String>, corner: Corner, opacity: f32) -> Self {
+        self.watermark = Some((text.into(), corner, opacity.clamp(0.0, 1.0)));
+        self
+    }
+
+    /// Shape labels and tooltips per [`text_shaping`](crate::utils::text_shaping)
+    pub fn advanced_text(mut self, advanced: bool) -> Self {
+        self.advanced_text = advanced;
+        self
+    }
+
+    /// Tint the whole chart faintly with the color returned for the latest visible
+    /// value, e.g. red while the current latency is over its SLA; `None` leaves it untinted
+    pub fn status_fn<F>(mut self, status_fn: F) -> Self
+    where
+        F: Fn(f64) -> Option<Color> + Send + Sync + 'static,
+    {
+        self.status_fn = Some(Box::new(status_fn));
+        self
+    }
+
+    /// Hand the visible (original index, value) pairs to `on_visible`, e.g. for a
+    /// data table synced to the chart. Called only when the plot is rebuilt, i.e.
+    /// when the data, window or size changed, not on every hover redraw.
+    pub fn on_visible<F>(mut self, on_visible: F) -> Self
+    where
+        F: Fn(&[(usize, f64)]) + Send + Sync + 'static,
+    {
+        self.on_visible = Some(Box::new(on_visible));
+        self
+    }
+
+    /// Pin an off-scale average line to the plot edge with an arrow (default on)
+    pub fn clamp_average(mut self, clamp: bool) -> Self {
+        self.clamp_average = clamp;
+        self
+    }
+
+    /// Shade one standard deviation either side of the average
+    pub fn average_band(mut self, band: bool) -> Self {
+        self.average_band = band;
+        self
+    }
+
+    /// Draw the median of the visible values as a dashed reference line next to
+    /// the average, to show skew at a glance
+    pub fn show_median(mut self, show: bool) -> Self {
+        self.show_median = show;
+        self
+    }
+
+    /// Keep the last hovered point highlighted after the cursor leaves
+    pub fn sticky_hover(mut self, sticky: bool) -> Self {
+        self.sticky_hover = sticky;
+        self
+    }
+
+    /// Add the hovered value's deviation from the average to its tooltip
+    pub fn hover_show_deviation(mut self, show: bool) -> Self {
+        self.hover_show_deviation = show;
+        self
+    }
+
+    /// How the hovered point is picked from the cursor (default [`HoverMode::Nearest`])
+    pub fn hover_mode(mut self, mode: HoverMode) -> Self {
+        self.hover_mode = mode;
+        self
+    }
+
+    pub fn base_points(mut self, points: f32) -> Self {
+        self.base_points = points;
+        self
+    }
+
+    pub fn zoom_range(mut self, min: f32, max: f32) -> Self {
+        self.zoom_min = min;
+        self.zoom_max = max;
+        self
+    }
+
+    pub fn zoom_min(mut self, min: f32) -> Self {
+        self.zoom_min = min;
+        self
+    }
+
+    pub fn zoom_max(mut self, max: f32) -> Self {
+        self.zoom_max = max;
+        self
+    }
+
+    pub fn point_color_fn<F>(mut self, color_fn: F) -> Self
+    where
+        F: Fn(&PointColorParams) -> Color + Send + Sync + 'static,
+    {
+        self.point_color_scheme = PointColorScheme::new_function(color_fn);
+        self
+    }
+
+    pub fn single_point_color(mut self, color: Color) -> Self {
+        self.point_color_scheme = PointColorScheme::new_single(color);
+        self
+    }
+
+    pub fn point_color_scheme(mut self, scheme: PointColorScheme) -> Self {
+        self.point_color_scheme = scheme;
+        self
+    }
+
+    /// Plot the cumulative distribution instead of the series: values sorted along x,
+    /// the percentage of samples at or below each value along y.
+    ///
+    /// Zoom and pan don't apply; hover indices refer to the sorted order.
+    pub fn cdf(mut self, cdf: bool) -> Self {
+        self.cdf = cdf;
+        self
+    }
+
+    /// Min and max of the full dataset (ignoring zoom), or `None` if it has no
+    /// finite values. Useful for giving several charts a common scale.
+    ///
+    /// Iterates the data once per call and isn't cached.
+    pub fn data_extent(&self) -> Option<(f64, f64)> {
+        self.datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .filter(|v| v.is_finite())
+            .fold(None, |extent, v| match extent {
+                None => Some((v, v)),
+                Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+            })
+    }
+
+    /// Keep the Y axis on the full dataset's range while zooming and panning, so
+    /// values don't jump vertically as the window moves. The extent is computed
+    /// here, once per graph; call it after anything that changes the data.
+    pub fn stable_y(mut self, stable: bool) -> Self {
+        self.stable_y = if stable { self.data_extent() } else { None };
+        self
+    }
+
+    /// Render the series as CSV (`index,value` header, one datapoint per line).
+    ///
+    /// Zoom and pan live in the widget state, so this exports the full series
+    /// rather than the visible window.
+    pub fn export_csv(&self) -> String {
+        let mut csv = String::from("index,value\n");
+        for (index, value) in self.datapoints.clone().enumerate() {
+            csv.push_str(&format!("{},{}\n", index, self.mapper.map(&value)));
+        }
+        csv
+    }
+
+    /// Mark an action as capturing its event, if [`LineGraph::capture_events`] allows it
+    fn capture(&self, action: canvas::Action<Interaction>) -> canvas::Action<Interaction> {
+        if self.capture_events {
+            action.and_capture()
+        } else {
+            action
+        }
+    }
+
+    /// Label text color: the override if set, otherwise the theme's text color
+    fn text_color(&self, palette: &iced::theme::palette::Extended) -> Color {
+        self.label_color.unwrap_or(palette.background.base.text)
+    }
+
+    /// Get the effective zoom value (external zoom if set, otherwise state zoom)
+    fn effective_zoom(&self, state: &LineGraphState) -> Zoom {
+        self.external_zoom.unwrap_or(state.zoom)
+    }
+
+    /// Left padding that fits the widest Y-axis label plus its gutter
+    pub(crate) fn auto_left_padding(&self, y_ticks: &[(f32, f64)]) -> f32 {
+        let widest = y_ticks
+            .iter()
+            .map(|(_, value)| estimate_text_width(&self.labels.format_y_axis(*value), Y_LABEL_SIZE))
+            .fold(0.0f32, f32::max);
+        widest + LABEL_GUTTER + 4.0
+    }
+
+    /// Y-axis ticks as (y position, value) pairs, top to bottom
+    pub(crate) fn y_tick_positions(
+        &self,
+        padding: f32,
+        chart_height: f32,
+        min_value: f64,
+        max_value: f64,
+    ) -> Vec<(f32, f64)> {
+        let value_range = max_value - min_value;
+        let values = match &self.y_ticks {
+            Some(ticks) => ticks
+                .iter()
+                .copied()
+                .filter(|v| *v >= min_value && *v <= max_value)
+                .collect(),
+            None if self.nice_ticks && !self.align_labels_to_grid => {
+                nice_ticks(min_value, max_value, 6)
+            }
+            None => {
+                let steps = if self.align_labels_to_grid {
+                    GRID_LINES
+                } else {
+                    5
+                };
+                return (0..=steps)
+                    .map(|i| {
+                        let y = padding + (i as f32 / steps as f32) * chart_height;
+                        let value = max_value - (i as f64 / steps as f64) * value_range;
+                        (y, value)
+                    })
+                    .collect();
+            }
+        };
+
+        let range_den: f64 = if value_range == 0.0 { 1.0 } else { value_range };
+        values
+            .into_iter()
+            .rev()
+            .map(|v| {
+                let normalized = (v - min_value) / range_den;
+                let y = padding + chart_height - (normalized as f32 * chart_height);
+                (y, v)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LabelConfig {
+    pub unit_suffix: String,
+    pub y_axis_decimals: u8,
+    pub tooltip_decimals: u8,
+    pub average_decimals: u8,
+    pub title: Option<String>,
+    /// Treat values as milliseconds and pick µs/ms/s per label instead of `unit_suffix`
+    pub auto_time_unit: bool,
+    /// Separator inserted between groups of three integer digits, if any
+    pub thousands_separator: Option<char>,
+    /// Round every label to a whole number with `unit_suffix`, ignoring the
+    /// decimals settings and `auto_time_unit`; for counts
+    pub integer_values: bool,
+}
+
+impl Default for LabelConfig {
+    fn default() -> Self {
+        Self {
+            unit_suffix: "".to_string(),
+            y_axis_decimals: 0,
+            tooltip_decimals: 2,
+            average_decimals: 1,
+            title: None,
+            auto_time_unit: false,
+            thousands_separator: None,
+            integer_values: false,
+        }
+    }
+}
+
+impl LabelConfig {
+    fn value_text(&self, value: f64, decimals: u8) -> String {
+        format_value(
+            value,
+            decimals,
+            &self.unit_suffix,
+            self.auto_time_unit,
+            self.integer_values,
+            self.thousands_separator,
+        )
+    }
+}
+
+/// How hovering picks the point under the cursor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HoverMode {
+    /// The point closest to the cursor, within a small radius
+    #[default]
+    Nearest,
+    /// The point closest to the cursor horizontally, at any height. Points
+    /// sharing its pixel column, as on dense or decimated lines, are settled by
+    /// the [`Tiebreak`].
+    NearestX(Tiebreak),
+}
+
+/// Which of several points in one pixel column [`HoverMode::NearestX`] picks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Tiebreak {
+    /// The most recent sample
+    #[default]
+    Latest,
+    /// The oldest sample
+    Earliest,
+    /// The highest value
+    Highest,
+    /// The lowest value
+    Lowest,
+}
+
+/// What the scroll wheel does over a line graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollBehavior {
+    /// Zoom and pan on every wheel event
+    #[default]
+    Zoom,
+    /// Ignore the wheel so it always reaches a parent scrollable
+    PassThrough,
+    /// Zoom and pan only while Ctrl (Cmd on macOS) is held; otherwise the wheel
+    /// scrolls the page, like embedded web maps
+    ZoomWithCtrl,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ZoomAnchor {
+    Start,
+    Middle,
+    End,
+}
+
+/// How zero (and, for gaps, non-finite) values are marked on the line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ZeroStyle {
+    /// Plot zeros like any other value
+    #[default]
+    Plain,
+    /// Draw zero points as hollow rings, even when points are otherwise hidden
+    Hollow,
+    /// Break the line at zero or missing (NaN/infinite) values, e.g. for "no data"
+    Gap,
+}
+
+/// Horizontal placement of the points across the chart width
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PointPlacement {
+    /// First point on the left edge, last on the right: `i / (len - 1)`
+    #[default]
+    EdgeToEdge,
+    /// Each point centered in its own equal slot: `(i + 0.5) / len`, like bar centers
+    Inset,
+}
+
+/// Scale of the X axis
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum XScale {
+    #[default]
+    Linear,
+    /// `log10` of the sample index + 1 (or of the value in CDF mode, with zero and
+    /// negative values pinned to the smallest positive one). Overrides [`PointPlacement`].
+    Log10,
+}
+
+impl XScale {
+    /// Position of `x` between `first` and `last` as a fraction of the axis
+    pub(crate) fn fraction(self, x: f64, first: f64, last: f64) -> f32 {
+        let (x, first, last) = match self {
+            XScale::Linear => (x, first, last),
+            XScale::Log10 => (x.log10(), first.log10(), last.log10()),
+        };
+        if last > first {
+            ((x - first) / (last - first)) as f32
+        } else {
+            0.0
+        }
+    }
+}
+
+/// How the full view thins a series with more points than pixel columns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecimationMode {
+    /// Draw every point
+    #[default]
+    None,
+    /// Every k-th point, plus the last
+    EveryNth,
+    /// The lowest and highest point of each pixel column, so spikes survive
+    MinMaxPerColumn,
+    /// The mean of each pixel column, placed at its middle
+    Average,
+}
+
+impl DecimationMode {
+    /// (index, value) pairs to draw from `values` across `columns` columns, or
+    /// `None` when every point should be drawn
+    pub(crate) fn decimate(self, values: &[f64], columns: usize) -> Option<Vec<(usize, f64)>> {
+        let count = values.len();
+        let columns = columns.max(1);
+        if self == DecimationMode::None || count <= columns {
+            return None;
+        }
+        // More points than columns, so no bucket is empty
+        let bucket = |column: usize| column * count / columns..(column + 1) * count / columns;
+
+        let kept = match self {
+            DecimationMode::None => return None,
+            DecimationMode::EveryNth => {
+                let mut kept: Vec<_> = (0..count)
+                    .step_by(count.div_ceil(columns))
+                    .map(|i| (i, values[i]))
+                    .collect();
+                if kept.last().map(|&(i, _)| i) != Some(count - 1) {
+                    kept.push((count - 1, values[count - 1]));
+                }
+                kept
+            }
+            DecimationMode::MinMaxPerColumn => (0..columns)
+                .flat_map(|column| {
+                    let range = bucket(column);
+                    let (mut low, mut high) = (range.start, range.start);
+                    for i in range {
+                        if values[i] < values[low] {
+                            low = i;
+                        }
+                        if values[i] > values[high] {
+                            high = i;
+                        }
+                    }
+                    // In series order, so the line runs through both
+                    let (first, second) = (low.min(high), low.max(high));
+                    std::iter::once((first, values[first]))
+                        .chain((second != first).then(|| (second, values[second])))
+                })
+                .collect(),
+            DecimationMode::Average => (0..columns)
+                .map(|column| {
+                    let range = bucket(column);
+                    let mean = values[range.clone()].iter().sum::<f64>() / range.len() as f64;
+                    (range.start + range.len() / 2, mean)
+                })
+                .collect(),
+        };
+        Some(kept)
+    }
+}
+
+/// Which end of the series a zoomed-in graph opens on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Anchor {
+    /// Oldest data first, for browsing a fixed dataset
+    Start,
+    /// Most recent data, following new points as they arrive
+    #[default]
+    End,
+}
+
+impl Anchor {
+    fn pan_mode(self) -> PanMode {
+        match self {
+            Anchor::Start => PanMode::Start,
+            Anchor::End => PanMode::End,
+        }
+    }
+}
+
+impl crate::utils::LabelFormatter for LabelConfig {
+    fn format_y_axis(&self, value: f64) -> String {
+        self.value_text(value, self.y_axis_decimals)
+    }
+
+    fn format_tooltip(&self, value: f64) -> String {
+        self.value_text(value, self.tooltip_decimals)
+    }
+
+    fn format_average_text(&self, value: f64) -> String {
+        format!("avg: {}", self.value_text(value, self.average_decimals))
+    }
+
+    fn format_title(&self, zoom: Zoom) -> Option<String> {
+        let title = self.title.as_ref()?;
+        let text = match zoom {
+            Zoom::Full => format!("{} (Full View)", title),
+            Zoom::Value(zoom_factor) => {
+                if zoom_factor.fract() == 0.0 {
+                    format!("{} (Zoom: {}x)", title, zoom_factor as u32)
+                } else {
+                    format!("{} (Zoom: {:.2}x)", title, zoom_factor)
+                }
+            }
+        };
+        Some(text)
+    }
+
+    fn format_subtitle(
+        &self,
+        zoom: Zoom,
+        start_idx: usize,
+        end_idx: usize,
+        count: usize,
+    ) -> String {
+        match zoom {
+            Zoom::Full => format!(
+                "(Showing all {} points: x {}..{})",
+                count, start_idx, end_idx
+            ),
+            Zoom::Value(_) => format!("(Showing {} points: x {}..{})", count, start_idx, end_idx),
+        }
+    }
+}
+
+impl<'a, I, T, M> canvas::Program<Interaction> for LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    type State = LineGraphState;
+
+    fn update(
+        &self,
+        state: &mut Self::State,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        if self.loading {
+            return None;
+        }
+
+        if !state.pan_seeded {
+            state.pan.mode = self.anchor.pan_mode();
+            state.pan_seeded = true;
+        }
+
+        let Some(shared) = &self.shared_view else {
+            let action = self.handle_event(state, event, bounds, cursor);
+            let action = self.animate_entries(state, action);
+            return self.settle_window(state, action);
+        };
+
+        // Work on the shared view, then publish whatever the event changed
+        shared.load_into(state);
+        let hovered = state.hovered_point;
+        let action = self.handle_event(state, event, bounds, cursor);
+        shared.set(state.zoom, state.pan.clone());
+        state.seen_view_generation.set(shared.generation());
+        if state.hovered_point != hovered {
+            // The other graphs draw the crosshair by series index, not window position
+            let hover = state.hovered_point.filter(|_| !self.cdf).and_then(|i| {
+                let layout = self.compute_layout(bounds.size(), state)?;
+                layout.indices.get(i).copied()
+            });
+            shared.set_hover(hover);
+        }
+        let action = self.animate_entries(state, action);
+        self.settle_window(state, action)
+    }
+
+    fn draw(
+        &self,
+        state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let overlay = &state.overlay;
+
+        // Another graph sharing the view may have moved it since this one last drew
+        let synced;
+        let state = match &self.shared_view {
+            Some(shared) => {
+                if state.seen_view_generation.get() != shared.generation() {
+                    state.seen_view_generation.set(shared.generation());
+                    self.cache.clear();
+                }
+                if state.seen_shared_hover.get() != shared.hover() {
+                    state.seen_shared_hover.set(shared.hover());
+                    overlay.clear();
+                }
+                let mut local = state.clone();
+                shared.load_into(&mut local);
+                synced = local;
+                &synced
+            }
+            None => state,
+        };
+
+        // Static layer: everything but the hover marker. The hover layer sits on top
+        // of it, so it is rebuilt whenever this one is.
+        let rebuilt = Cell::new(false);
+        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
+            rebuilt.set(true);
+            let bounds = frame.size();
+            if self.clear_background {
+                let background = self
+                    .background_color
+                    .unwrap_or(theme.extended_palette().background.base.color);
+                frame.fill_rectangle(Point::ORIGIN, bounds, background);
+            }
+            if let Some((text, corner, opacity)) = &self.watermark {
+                let color = self
+                    .text_color(theme.extended_palette())
+                    .scale_alpha(*opacity);
+                draw_watermark(
+                    frame,
+                    text,
+                    *corner,
+                    color,
+                    text_shaping(self.advanced_text),
+                );
+            }
+            let palette = theme.extended_palette();
+
+            if self.cdf {
+                self.draw_cdf(frame, bounds, state, theme);
+                return;
+            }
+
+            let Some(layout) = self.compute_layout(bounds, state) else {
+                return;
+            };
+            let LineLayout {
+                top,
+                left,
+                chart_width,
+                chart_height,
+                min_value,
+                max_value,
+                average,
+                zoom: effective_zoom,
+                ..
+            } = layout;
+            let value_range = max_value - min_value;
+            if let Some(shared) = &self.shared_view {
+                shared.record_plot(SharedPlot {
+                    window: layout.window.clone(),
+                    left,
+                    chart_width,
+                });
+            }
+            let points = &layout.points;
+            let values = &layout.values;
+
+            if let Some(status_fn) = &self.status_fn
+                && let Some(tint) = values
+                    .iter()
+                    .rfind(|v| v.is_finite())
+                    .and_then(|latest| status_fn(*latest))
+            {
+                frame.fill_rectangle(Point::ORIGIN, bounds, tint.scale_alpha(0.12));
+            }
+
+            let y_ticks = self.y_tick_positions(top, chart_height, min_value, max_value);
+
+            // The grid spaces both axes from `top`; shift it onto the plot's left edge
+            let draw_grid = |frame: &mut canvas::Frame| {
+                frame.with_save(|frame| {
+                    frame.translate(Vector::new(left - top, 0.0));
+                    self.draw_grid(frame, top, chart_width, chart_height, &y_ticks, palette);
+                });
+            };
+
+            if self.show_grid && self.grid_layer == GridLayer::Behind {
+                draw_grid(frame);
+
+                // Semi-opaque background between grid and data so gridlines recede
+                if self.grid_fade > 0.0 {
+                    frame.fill_rectangle(
+                        Point::new(left, top),
+                        Size::new(chart_width, chart_height),
+                        palette.background.base.color.scale_alpha(self.grid_fade),
+                    );
+                }
+            }
+
+            self.draw_value_bands(frame, &layout);
+
+            if let Some((_, color)) = &self.baseline_series
+                && layout.baseline_points.len() > 1
+            {
+                let ghost = canvas::Path::new(|builder| {
+                    builder.move_to(layout.baseline_points[0]);
+                    for point in &layout.baseline_points[1..] {
+                        builder.line_to(*point);
+                    }
+                });
+                frame.stroke(
+                    &ghost,
+                    canvas::Stroke::default()
+                        .with_color(color.scale_alpha(0.35))
+                        .with_width(self.line_width),
+                );
+            }
+
+            if self.average_band {
+                self.draw_average_band(frame, &layout);
+            }
+
+            if layout.band.len() > 1 {
+                self.draw_band(frame, &layout.band, palette);
+            }
+
+            // Draw the line, split into runs around zero/missing values in gap mode
+            // and around time gaps
+            if self.as_columns {
+                self.draw_columns(frame, &layout, palette);
+            } else if self.zero_style == ZeroStyle::Gap || !layout.breaks.is_empty() {
+                for run in self.line_runs(&layout) {
+                    self.draw_line(frame, &points[run], palette);
+                }
+            } else if points.len() > 1 {
+                self.draw_line(frame, points, palette);
+            }
+
+            // Points are hidden in full view, so mark zeros on their own there
+            let points_shown = self.show_points && effective_zoom.is_value();
+            if self.zero_style == ZeroStyle::Hollow && !points_shown {
+                let point_color = self.base_line_color(palette);
+                for (point, _) in points.iter().zip(values).filter(|(_, v)| **v == 0.0) {
+                    self.draw_hollow_point(frame, *point, self.point_radius, point_color, palette);
+                }
+            }
+
+            // Draw data points if enabled (but not in full view), or just the extrema
+            if self.mark_extrema {
+                self.draw_extrema(frame, points, values, average, theme);
+            } else if points_shown {
+                self.draw_points(frame, &layout, &state.point_entries, theme);
+            }
+
+            if self.show_grid && self.grid_layer == GridLayer::Front {
+                draw_grid(frame);
+            }
+
+            // Visible datapoints as (usize, f64) for the host and the draw_labels method
+            let visible_datapoints_f64: Vec<(usize, f64)> = layout
+                .indices
+                .iter()
+                .copied()
+                .zip(values.iter().copied())
+                .collect();
+            if let Some(on_visible) = &self.on_visible {
+                on_visible(&visible_datapoints_f64);
+            }
+
+            // Draw labels if enabled
+            if self.show_labels {
+                self.draw_labels(
+                    frame,
+                    bounds,
+                    top,
+                    left,
+                    chart_width,
+                    chart_height,
+                    min_value,
+                    average,
+                    value_range,
+                    &y_ticks,
+                    palette,
+                    effective_zoom,
+                    &visible_datapoints_f64,
+                );
+            }
+
+            if self.show_median {
+                let median = median(values);
+                let range_den = if value_range == 0.0 { 1.0 } else { value_range };
+                let median_y =
+                    top + chart_height - ((median - min_value) / range_den) as f32 * chart_height;
+                draw_dashed_average(
+                    frame,
+                    left,
+                    chart_width,
+                    median_y,
+                    MEDIAN_COLOR,
+                    MEDIAN_DASH,
+                    self.average_glow,
+                );
+                // Left side, away from the average label on the right
+                frame.fill_text(canvas::Text {
+                    content: format!("med: {}", self.labels.format_y_axis(median)),
+                    position: Point::new(left + 4.0, median_y - 4.0),
+                    color: MEDIAN_COLOR,
+                    size: Pixels(11.0),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_y: Bottom,
+                    ..canvas::Text::default()
+                });
+            }
+
+            if self.live {
+                self.draw_live_indicator(frame, &layout, theme);
+            }
+
+            if self.label_endpoints {
+                self.draw_endpoint_labels(frame, &layout, bounds, palette);
+            }
+
+            self.draw_overview(frame, &layout, theme);
+
+            if self.show_data_info {
+                frame.fill_text(canvas::Text {
+                    content: format!("showing {} of {}", layout.window.len(), layout.total),
+                    position: Point::new(left + chart_width, top - 4.0),
+                    color: self.text_color(palette).scale_alpha(0.6),
+                    size: Pixels(10.0),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_x: Right.into(),
+                    align_y: Bottom,
+                    ..canvas::Text::default()
+                });
+            }
+        });
+        if rebuilt.get() {
+            overlay.clear();
+        }
+
+        let hover = overlay.draw(renderer, bounds.size(), |frame| {
+            if let Some(index) = self.shared_view.as_ref().and_then(SharedView::hover)
+                && !self.cdf
+                && let Some(layout) = self.compute_layout(frame.size(), state)
+            {
+                self.draw_crosshair(frame, &layout, index, theme);
+            }
+            if self.cdf {
+                if let Some(index) = state.hovered_point {
+                    self.draw_cdf_hover(frame, frame.size(), state, index);
+                }
+            } else if self.show_points
+                && (state.hovered_point.is_some() || !state.pinned_points.is_empty())
+                && let Some(layout) = self.compute_layout(frame.size(), state)
+                && layout.zoom.is_value()
+            {
+                self.draw_point_tooltips(frame, &layout, state, theme);
+            }
+        });
+
+        if self.loading {
+            let mut frame = canvas::Frame::new(renderer, bounds.size());
+            let palette = theme.extended_palette();
+            draw_loading_overlay(&mut frame, palette, self.text_color(palette));
+            return vec![geometry, frame.into_geometry()];
+        }
+
+        vec![geometry, hover]
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Self::State,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> mouse::Interaction {
+        let synced;
+        let state = match &self.shared_view {
+            Some(shared) => {
+                let mut local = state.clone();
+                shared.load_into(&mut local);
+                synced = local;
+                &synced
+            }
+            None => state,
+        };
+        self.overview_interaction(state, bounds, cursor)
+            .unwrap_or_default()
+    }
+}
+
+impl<'a, I, T, M> LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// Whether the scroll wheel zooms and pans right now, per [`ScrollBehavior`]
+    fn wheel_active(&self, state: &LineGraphState) -> bool {
+        match self.scroll_behavior {
+            ScrollBehavior::Zoom => true,
+            ScrollBehavior::PassThrough => false,
+            ScrollBehavior::ZoomWithCtrl => state.ctrl_down,
+        }
+    }
+
+    /// With [`Self::point_entry_animation`], start animating points appended since the
+    /// last update and keep redrawing until they have all grown in
+    fn animate_entries(
+        &self,
+        state: &mut LineGraphState,
+        action: Option<canvas::Action<Interaction>>,
+    ) -> Option<canvas::Action<Interaction>> {
+        let Some(duration) = self.point_entry else {
+            return action;
+        };
+        let now = Instant::now();
+        if let Some(seen) = state.seen_len
+            && self.len > seen
+        {
+            let fresh = (seen..self.len).rev().take(MAX_POINT_ENTRIES);
+            state.point_entries.extend(fresh.map(|index| (index, now)));
+        }
+        state.seen_len = Some(self.len);
+
+        let animating = !state.point_entries.is_empty();
+        state
+            .point_entries
+            .retain(|(_, arrived)| now.duration_since(*arrived) < duration);
+        if !animating {
+            return action;
+        }
+        // Redraw once more after the last one finishes, at full size
+        self.cache.clear();
+        action.or_else(|| Some(canvas::Action::request_redraw()))
+    }
+
+    /// Scale for the marker of series point `index`: eases from 0 to 1 over the
+    /// entry animation, 1 when it isn't animating
+    fn entry_scale(&self, entries: &[(usize, Instant)], index: usize) -> f32 {
+        let Some(duration) = self.point_entry.filter(|d| !d.is_zero()) else {
+            return 1.0;
+        };
+        match entries.iter().find(|(i, _)| *i == index) {
+            Some((_, arrived)) => {
+                let t = (arrived.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0);
+                1.0 - (1.0 - t) * (1.0 - t)
+            }
+            None => 1.0,
+        }
+    }
+
+    /// With [`Self::report_window`], publish the visible window once it has been
+    /// unchanged for [`WINDOW_SETTLE`], on an event the graph didn't otherwise act on
+    fn settle_window(
+        &self,
+        state: &mut LineGraphState,
+        action: Option<canvas::Action<Interaction>>,
+    ) -> Option<canvas::Action<Interaction>> {
+        if !self.report_window || self.len == 0 {
+            return action;
+        }
+        let window = self.window_indices(
+            self.len,
+            state,
+            self.visible_count(self.len, self.effective_zoom(state)),
+        );
+        if state.reported_window.as_ref() == Some(&window) {
+            state.settling_window = None;
+            return action;
+        }
+
+        let now = Instant::now();
+        let settles_at = match &state.settling_window {
+            Some((settling, at)) if *settling == window => *at,
+            _ => {
+                let at = now + WINDOW_SETTLE;
+                state.settling_window = Some((window.clone(), at));
+                at
+            }
+        };
+        if action.is_some() {
+            // Its redraw brings another event to check on
+            action
+        } else if now >= settles_at {
+            state.settling_window = None;
+            state.reported_window = Some(window.clone());
+            Some(canvas::Action::publish(Interaction::WindowChanged(window)))
+        } else {
+            Some(canvas::Action::request_redraw_at(settles_at))
+        }
+    }
+
+    /// Handle an event against the graph's own state
+    fn handle_event(
+        &self,
+        state: &mut LineGraphState,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        if let Some(action) = self.handle_overview_event(state, event, bounds, cursor) {
+            return Some(action);
+        }
+
+        match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(mods)) => {
+                state.ctrl_down = mods.control() || mods.command();
+                let new_shift = mods.shift();
+                if state.shift_down != new_shift {
+                    state.shift_down = new_shift;
+                    return Some(canvas::Action::request_redraw());
+                }
+                None
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                // Only enable hover when zoomed in (not in full view) and points are visible
+                let effective_zoom = self.effective_zoom(state);
+                if self.cdf || effective_zoom.is_value() {
+                    if let Some(cursor_position) = cursor.position_in(bounds) {
+                        let new_hovered = self.find_nearest_point(cursor_position, bounds, state);
+
+                        if state.hovered_point != new_hovered {
+                            state.hovered_point = new_hovered;
+                            state.overlay.clear();
 
                             if let Some(point_index) = new_hovered {
                                 return Some(canvas::Action::publish(Interaction::PointHovered(
@@ -403,33 +1958,74 @@ where
                                 return Some(canvas::Action::request_redraw());
                             }
                         }
-                    } else if state.hovered_point.is_some() {
+                    } else if state.hovered_point.is_some() && !self.sticky_hover {
                         state.hovered_point = None;
-                        self.cache.clear();
+                        state.overlay.clear();
                         return Some(canvas::Action::request_redraw());
                     }
                 } else {
                     // In full view, clear any existing hover state
                     if state.hovered_point.is_some() {
                         state.hovered_point = None;
-                        self.cache.clear();
+                        state.overlay.clear();
                         return Some(canvas::Action::request_redraw());
                     }
                 }
                 None
             }
-            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
-                if let Some(cursor_position) = cursor.position_in(bounds)
-                    && let Some(point_index) =
-                        self.find_nearest_point(cursor_position, bounds, state)
-                {
-                    return Some(canvas::Action::publish(Interaction::PointClicked(
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key: keyboard::Key::Named(named @ (key::Named::ArrowLeft | key::Named::ArrowRight)),
+                ..
+            }) if state.focused && self.effective_zoom(state).is_value() => {
+                let forward = *named == key::Named::ArrowRight;
+                let point_index = self.step_hovered_point(state, forward)?;
+                state.overlay.clear();
+                Some(
+                    self.capture(canvas::Action::publish(Interaction::PointHovered(
                         point_index,
-                    )));
+                    ))),
+                )
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                // Clicking the graph focuses it for keyboard navigation; clicking elsewhere blurs it
+                state.focused = cursor.is_over(bounds);
+
+                let cursor_position = cursor.position_in(bounds)?;
+                let point_index = self.find_nearest_point(cursor_position, bounds, state)?;
+                if self.pin_on_click && !self.cdf {
+                    let index = *self
+                        .compute_layout(bounds.size(), state)?
+                        .indices
+                        .get(point_index)?;
+                    state.overlay.clear();
+                    if let Some(pos) = state.pinned_points.iter().position(|&p| p == index) {
+                        state.pinned_points.remove(pos);
+                        return Some(canvas::Action::publish(Interaction::PointUnpinned(index)));
+                    }
+                    state.pinned_points.push(index);
+                    return Some(canvas::Action::publish(Interaction::PointPinned(index)));
                 }
-                None
+                Some(canvas::Action::publish(Interaction::PointClicked(
+                    point_index,
+                )))
             }
-            Event::Mouse(mouse::Event::WheelScrolled { delta }) if cursor.is_over(bounds) => {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => {
+                let position = cursor.position_over(bounds)?;
+                let index = cursor
+                    .position_in(bounds)
+                    .and_then(|p| self.find_nearest_point(p, bounds, state));
+                let value = index.and_then(|i| self.value_at(i, bounds, state));
+                Some(
+                    self.capture(canvas::Action::publish(Interaction::ContextMenu {
+                        index,
+                        value,
+                        position,
+                    })),
+                )
+            }
+            Event::Mouse(mouse::Event::WheelScrolled { delta })
+                if self.zoom_enabled && self.wheel_active(state) && cursor.is_over(bounds) =>
+            {
                 // Only handle zoom/pan changes if external zoom is not set
                 if self.external_zoom.is_none() {
                     match delta {
@@ -464,6 +2060,10 @@ where
                                     } else if pan_amount < 0.0 {
                                         start = start.saturating_sub(step);
                                     }
+                                    // Already at an edge: let the parent have the scroll
+                                    if self.scroll_passthrough && start == range.start {
+                                        return None;
+                                    }
                                     // Snap to edges so subsequent zoom can infer anchor from position
                                     state.pan.mode = if start == 0 {
                                         PanMode::Start
@@ -473,7 +2073,7 @@ where
                                         PanMode::Absolute(start)
                                     };
                                     self.cache.clear();
-                                    return Some(canvas::Action::request_redraw().and_capture());
+                                    return Some(self.capture(canvas::Action::request_redraw()));
                                 }
                                 // In full view, nothing to pan
                                 return None;
@@ -481,7 +2081,28 @@ where
 
                             // Otherwise: vertical zoom
                             if *y != 0.0 {
-                                let new_zoom = if y.is_sign_positive() {
+                                let lines = match delta {
+                                    mouse::ScrollDelta::Lines { .. } => *y,
+                                    mouse::ScrollDelta::Pixels { .. } => *y / PIXELS_PER_LINE,
+                                };
+                                // Reset on direction change so reversing responds immediately
+                                if state.scroll_accumulator.signum() != lines.signum() {
+                                    state.scroll_accumulator = 0.0;
+                                }
+                                state.scroll_accumulator += lines * self.zoom_step;
+                                if state.scroll_accumulator.abs() < 1.0 {
+                                    return (self.capture_events && !self.scroll_passthrough)
+                                        .then(canvas::Action::capture);
+                                }
+                                state.scroll_accumulator %= 1.0;
+
+                                let new_zoom = if self.integer_zoom {
+                                    if y.is_sign_positive() {
+                                        state.zoom.increment_integer(self.zoom_max)
+                                    } else {
+                                        state.zoom.decrement_integer()
+                                    }
+                                } else if y.is_sign_positive() {
                                     // Zooming in
                                     state.zoom.increment_with_limits(self.zoom_max)
                                 } else {
@@ -562,7 +2183,15 @@ where
 
                                     state.zoom = new_zoom;
                                     self.cache.clear();
-                                    return Some(canvas::Action::request_redraw().and_capture());
+
+                                    let at_limit = new_zoom == Zoom::Full
+                                        || new_zoom == Zoom::Value(self.zoom_max);
+                                    let action = canvas::Action::request_redraw();
+                                    return Some(if self.scroll_passthrough && at_limit {
+                                        action
+                                    } else {
+                                        self.capture(action)
+                                    });
                                 }
                             }
                         }
@@ -574,189 +2203,80 @@ where
         }
     }
 
-    fn draw(
-        &self,
-        state: &Self::State,
-        renderer: &Renderer,
-        theme: &Theme,
-        bounds: Rectangle,
-        _cursor: mouse::Cursor,
-    ) -> Vec<canvas::Geometry> {
-        let geometry = self.cache.draw(renderer, bounds.size(), |frame| {
-            let bounds = frame.size();
-            let palette = theme.extended_palette();
-
-            // Collect datapoints to work with them (enumerate internally)
-            let all_datapoints: Vec<_> = self.datapoints.clone().enumerate().collect();
-
-            if all_datapoints.is_empty() {
-                return;
-            }
-
-            // Calculate chart dimensions
-            let padding = 40.0;
-            let chart_width = bounds.width - 2.0 * padding;
-            let chart_height = bounds.height - 2.0 * padding;
-
-            // Apply zoom to determine how many points to show
-            let effective_zoom = self.effective_zoom(state);
-            let max_visible_points = self.visible_count(all_datapoints.len(), effective_zoom);
-
-            let range = self.window_indices(all_datapoints.len(), state, max_visible_points);
-            let visible_datapoints = &all_datapoints[range];
-
-            if visible_datapoints.is_empty() {
-                return;
-            }
-
-            // Find min/max values for proper scaling from visible data
-            let values: Vec<f64> = visible_datapoints
-                .iter()
-                .map(|(_, v)| self.mapper.map(v))
-                .collect();
-            let min_value = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-            let max_value = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-            let value_range = max_value - min_value;
-            let range_den: f64 = if value_range == 0.0 { 1.0 } else { value_range };
-
-            let average = values.iter().sum::<f64>() / values.len() as f64;
-
-            // Create points for the line (using visible datapoints)
-            let points: Vec<Point> = visible_datapoints
-                .iter()
-                .enumerate()
-                .map(|(i, (_, value))| {
-                    let denom = (visible_datapoints.len().saturating_sub(1)).max(1) as f32;
-                    let x = padding + (i as f32 / denom) * chart_width;
-                    let value_f64 = self.mapper.map(value);
-                    let normalized_value = (value_f64 - min_value) / range_den;
-                    let y = padding + chart_height - (normalized_value as f32 * chart_height);
-                    Point::new(x, y)
-                })
-                .collect();
-
-            // Draw grid if enabled
-            if self.show_grid {
-                self.draw_grid(frame, padding, chart_width, chart_height, palette);
-            }
-
-            // Draw the line
-            if points.len() > 1 {
-                self.draw_line(frame, &points, palette);
-            }
-
-            // Draw data points if enabled (but not in full view)
-            if self.show_points && effective_zoom.is_value() {
-                let visible_indices: Vec<usize> =
-                    visible_datapoints.iter().map(|(i, _)| *i).collect();
-                self.draw_points(
-                    frame,
-                    &points,
-                    &values,
-                    &visible_indices,
-                    &state.hovered_point,
-                    average,
-                    theme,
-                );
-            }
-
-            // Draw labels if enabled
-            if self.show_labels {
-                // Convert visible datapoints to (usize, f64) for the draw_labels method
-                let visible_datapoints_f64: Vec<(usize, f64)> = visible_datapoints
-                    .iter()
-                    .map(|(i, v)| (*i, self.mapper.map(v)))
-                    .collect();
-
-                self.draw_labels(
-                    frame,
-                    bounds,
-                    padding,
-                    chart_width,
-                    chart_height,
-                    min_value,
-                    max_value,
-                    average,
-                    value_range,
-                    palette,
-                    effective_zoom,
-                    &visible_datapoints_f64,
-                );
-            }
-        });
-
-        vec![geometry]
-    }
-}
-
-impl<'a, I, T, M> LineGraph<'a, I, T, M>
-where
-    I: Iterator<Item = T> + Clone + 'a,
-    M: ValueMapper<T>,
-{
-    fn find_nearest_point(
-        &self,
-        cursor_pos: Point,
-        bounds: Rectangle,
-        state: &LineGraphState,
-    ) -> Option<usize> {
-        let padding = 40.0;
-        let chart_width = bounds.width - 2.0 * padding;
-
-        // We need to use the same logic as the draw method to calculate visible datapoints
-        let all_datapoints: Vec<_> = self.datapoints.clone().enumerate().collect();
-
-        if all_datapoints.is_empty() {
-            return None;
-        }
-
-        // Use the same zoom calculation logic as the draw method
-        let effective_zoom = self.effective_zoom(state);
-        let max_visible_points = self.visible_count(all_datapoints.len(), effective_zoom);
-
-        let range = self.window_indices(all_datapoints.len(), state, max_visible_points);
-        let visible_datapoints = &all_datapoints[range];
-
-        if visible_datapoints.is_empty() {
+    /// Move the hovered point one step left or right, panning the window when the
+    /// selection leaves it. Returns the new window-relative hovered index.
+    fn step_hovered_point(&self, state: &mut LineGraphState, forward: bool) -> Option<usize> {
+        let total = self.len;
+        let visible = self.visible_count(total, self.effective_zoom(state));
+        let range = self.window_indices(total, state, visible);
+        if range.is_empty() {
             return None;
         }
 
-        // Find the nearest point using the same coordinate calculation as drawing
-        let mut closest_index = None;
-        let mut closest_distance = f32::INFINITY;
-
-        // We also need the y-coordinates to calculate true distance
-        let values: Vec<f64> = visible_datapoints
-            .iter()
-            .map(|(_, v)| self.mapper.map(v))
-            .collect();
+        // Without a current selection, start from the edge we're moving away from
+        let target = match state.hovered_point.map(|i| range.start + i) {
+            Some(current) if forward => (current + 1).min(total - 1),
+            Some(current) => current.saturating_sub(1),
+            None if forward => range.start,
+            None => range.end - 1,
+        };
 
-        if values.is_empty() {
-            return None;
+        if !range.contains(&target) {
+            let max_start = total.saturating_sub(visible);
+            let start = if target < range.start {
+                target
+            } else {
+                target + 1 - visible
+            };
+            state.pan.mode = if start == 0 {
+                PanMode::Start
+            } else if start >= max_start {
+                PanMode::End
+            } else {
+                PanMode::Absolute(start)
+            };
+            // The plot itself moved, not just the hover marker
+            self.cache.clear();
         }
 
-        let min_value = values.iter().fold(f64::INFINITY, |a, &b| a.min(b));
-        let max_value = values.iter().fold(f64::NEG_INFINITY, |a, &b| a.max(b));
-        let value_range = max_value - min_value;
-        let range_den: f64 = if value_range == 0.0 { 1.0 } else { value_range };
+        let range = self.window_indices(total, state, visible);
+        let relative = target - range.start;
+        state.hovered_point = Some(relative);
+        Some(relative)
+    }
 
-        let chart_height = bounds.height - 2.0 * padding;
+    /// Value of the point `index` as reported by [`Self::find_nearest_point`]
+    fn value_at(&self, index: usize, bounds: Rectangle, state: &LineGraphState) -> Option<f64> {
+        if self.cdf {
+            return self.cdf_value(state, index);
+        }
+        self.compute_layout(bounds.size(), state)?
+            .values
+            .get(index)
+            .copied()
+    }
 
-        for (i, (_original_index, _)) in visible_datapoints.iter().enumerate() {
-            let denom = (visible_datapoints.len().saturating_sub(1)).max(1) as f32;
-            let x = padding + (i as f32 / denom) * chart_width;
+    fn find_nearest_point(
+        &self,
+        cursor_pos: Point,
+        bounds: Rectangle,
+        state: &LineGraphState,
+    ) -> Option<usize> {
+        if self.cdf {
+            return self.find_nearest_cdf_point(cursor_pos, bounds, state);
+        }
 
-            // Calculate y coordinate using same logic as drawing
-            let value_f64 = values[i];
-            let normalized_value = (value_f64 - min_value) / range_den;
-            let y = padding + chart_height - (normalized_value as f32 * chart_height);
+        let layout = self.compute_layout(bounds.size(), state)?;
 
-            // Calculate distance to the actual point (both x and y)
-            let dx = cursor_pos.x - x;
-            let dy = cursor_pos.y - y;
-            let distance = (dx * dx + dy * dy).sqrt();
+        if let HoverMode::NearestX(tiebreak) = self.hover_mode {
+            return Self::nearest_x(&layout, cursor_pos.x, tiebreak);
+        }
 
-            // Use a slightly larger radius for better usability
+        // Nearest point by true distance, within a slightly generous radius for usability
+        let mut closest_index = None;
+        let mut closest_distance = f32::INFINITY;
+        for (i, point) in layout.points.iter().enumerate() {
+            let distance = point.distance(cursor_pos);
             if distance <= 20.0 && distance < closest_distance {
                 closest_distance = distance;
                 closest_index = Some(i);
@@ -766,50 +2286,220 @@ where
         closest_index
     }
 
+    /// Point closest to `x` horizontally, settling points in the same pixel
+    /// column by `tiebreak`; `None` when `x` is well outside the plot
+    fn nearest_x(layout: &LineLayout, x: f32, tiebreak: Tiebreak) -> Option<usize> {
+        if x < layout.left - 20.0 || x > layout.left + layout.chart_width + 20.0 {
+            return None;
+        }
+        let finite = || {
+            layout
+                .points
+                .iter()
+                .zip(&layout.values)
+                .enumerate()
+                .filter(|(_, (_, value))| value.is_finite())
+        };
+        let (_, (nearest, _)) = finite()
+            .min_by(|(_, (a, _)), (_, (b, _))| (a.x - x).abs().total_cmp(&(b.x - x).abs()))?;
+        let column = nearest.x.floor();
+        let tied = finite().filter(|(_, (point, _))| point.x.floor() == column);
+        match tiebreak {
+            Tiebreak::Latest => tied.max_by_key(|(i, _)| *i),
+            Tiebreak::Earliest => tied.min_by_key(|(i, _)| *i),
+            Tiebreak::Highest => tied.max_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b)),
+            Tiebreak::Lowest => tied.min_by(|(_, (_, a)), (_, (_, b))| a.total_cmp(b)),
+        }
+        .map(|(i, _)| i)
+    }
+
     fn draw_grid(
         &self,
         frame: &mut canvas::Frame,
         padding: f32,
         chart_width: f32,
         chart_height: f32,
+        y_ticks: &[(f32, f64)],
         palette: &iced::theme::palette::Extended,
     ) {
+        // Explicit ticks replace the evenly spaced horizontal lines
+        let horizontal_positions = if self.y_ticks.is_some() {
+            y_ticks.iter().map(|(y, _)| *y).collect()
+        } else {
+            Vec::new()
+        };
         let config = GridConfig {
             padding,
             chart_width,
             chart_height,
-            horizontal_lines: 10,
+            horizontal_lines: GRID_LINES,
+            horizontal_positions,
             vertical_lines: 10,
+            axes: self.grid_axes,
+            color: self.grid_color,
+            minor_subdivisions: self.minor_subdivisions,
             ..GridConfig::default()
         };
+        // Over the data the grid needs more contrast to read as an outline
+        let config = match self.grid_layer {
+            GridLayer::Behind => config,
+            GridLayer::Front => GridConfig {
+                major_alpha: 0.4,
+                minor_alpha: 0.15,
+                ..config
+            },
+        };
         draw_grid(frame, &config, palette);
     }
 
+    /// Average ± one standard deviation as a translucent strip in the average color
+    fn draw_average_band(&self, frame: &mut canvas::Frame, layout: &LineLayout) {
+        if !layout.std_dev.is_finite() {
+            return;
+        }
+        let range = match layout.max_value - layout.min_value {
+            0.0 => 1.0,
+            range => range,
+        };
+        let bottom = layout.top + layout.chart_height;
+        let y = |value: f64| {
+            (bottom - ((value - layout.min_value) / range) as f32 * layout.chart_height)
+                .clamp(layout.top, bottom)
+        };
+        let (upper, lower) = (
+            y(layout.average + layout.std_dev),
+            y(layout.average - layout.std_dev),
+        );
+        frame.fill_rectangle(
+            Point::new(layout.left, upper),
+            Size::new(layout.chart_width, lower - upper),
+            self.average_color
+                .unwrap_or(Color::from_rgb(1.0, 0.6, 0.2))
+                .scale_alpha(0.12),
+        );
+    }
+
+    /// Fill between the upper and lower band edges in a faint line color
+    fn draw_band(
+        &self,
+        frame: &mut canvas::Frame,
+        band: &[(Point, Point)],
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let path = canvas::Path::new(|builder| {
+            builder.move_to(band[0].1);
+            for (_, upper) in &band[1..] {
+                builder.line_to(*upper);
+            }
+            for (lower, _) in band.iter().rev() {
+                builder.line_to(*lower);
+            }
+            builder.close();
+        });
+        frame.fill(&path, self.base_line_color(palette).scale_alpha(0.2));
+    }
+
+    /// Shaded bands for [`LineGraph::value_bands`], clipped to the visible range
+    fn draw_value_bands(&self, frame: &mut canvas::Frame, layout: &LineLayout) {
+        let range_den = match layout.max_value - layout.min_value {
+            0.0 => 1.0,
+            range => range,
+        };
+        let to_y = |value: f64| {
+            layout.top + layout.chart_height
+                - ((value - layout.min_value) / range_den) as f32 * layout.chart_height
+        };
+        for &(from, to, color) in &self.value_bands {
+            let low = from.min(to).max(layout.min_value);
+            let high = from.max(to).min(layout.max_value);
+            if low >= high {
+                continue;
+            }
+            let (top, bottom) = (to_y(high), to_y(low));
+            frame.fill_rectangle(
+                Point::new(layout.left, top),
+                Size::new(layout.chart_width, bottom - top),
+                color,
+            );
+        }
+    }
+
+    /// One column per point, rising from zero (or the nearest plot edge when zero
+    /// is out of range) to the point's value
+    fn draw_columns(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let bottom = layout.top + layout.chart_height;
+        let value_range = layout.max_value - layout.min_value;
+        let range_den = if value_range == 0.0 { 1.0 } else { value_range };
+        let zero_y = bottom - ((0.0 - layout.min_value) / range_den) as f32 * layout.chart_height;
+        let baseline = zero_y.clamp(layout.top, bottom);
+
+        let spacing = layout.chart_width / layout.points.len().max(1) as f32;
+        let width = (spacing * 0.5).clamp(1.0, 8.0);
+        let color = self.base_line_color(palette);
+
+        for point in &layout.points {
+            let top = point.y.min(baseline);
+            frame.fill_rectangle(
+                Point::new(point.x - width / 2.0, top),
+                Size::new(width, (point.y - baseline).abs()),
+                color,
+            );
+        }
+    }
+
     fn draw_line(
         &self,
         frame: &mut canvas::Frame,
         points: &[Point],
-        _palette: &iced::theme::palette::Extended,
+        palette: &iced::theme::palette::Extended,
     ) {
         if points.len() < 2 {
             return;
         }
 
+        // Equal values share a y, so points inside a flat stretch add nothing to the path
+        let collapsed: Vec<Point>;
+        let points = if self.collapse_flats {
+            collapsed = points
+                .iter()
+                .enumerate()
+                .filter(|&(i, point)| {
+                    i == 0
+                        || i + 1 == points.len()
+                        || points[i - 1].y != point.y
+                        || points[i + 1].y != point.y
+                })
+                .map(|(_, point)| *point)
+                .collect();
+            &collapsed[..]
+        } else {
+            points
+        };
+
         // Draw shadow/glow effect behind the main line
-        let mut shadow_path_builder = canvas::path::Builder::new();
-        shadow_path_builder.move_to(Point::new(points[0].x + 1.0, points[0].y + 1.0));
+        if self.shadow.enabled {
+            let offset = self.shadow.offset;
+            let mut shadow_path_builder = canvas::path::Builder::new();
+            shadow_path_builder.move_to(points[0] + offset);
 
-        for point in points.iter().skip(1) {
-            shadow_path_builder.line_to(Point::new(point.x + 1.0, point.y + 1.0));
-        }
+            for point in points.iter().skip(1) {
+                shadow_path_builder.line_to(*point + offset);
+            }
 
-        let shadow_path = shadow_path_builder.build();
-        frame.stroke(
-            &shadow_path,
-            canvas::Stroke::default()
-                .with_color(Color::BLACK.scale_alpha(0.2))
-                .with_width(self.line_width + 1.0),
-        );
+            let shadow_path = shadow_path_builder.build();
+            frame.stroke(
+                &shadow_path,
+                self.line_stroke(
+                    Color::BLACK.scale_alpha(self.shadow.alpha),
+                    self.line_width + 1.0,
+                ),
+            );
+        }
 
         // Draw main line with smooth appearance
         let mut path_builder = canvas::path::Builder::new();
@@ -820,70 +2510,422 @@ where
         }
 
         let path = path_builder.build();
-        let line_color = self.line_color.unwrap_or(Color::from_rgb(0.2, 0.6, 1.0)); // Nice blue
+        let line_color = self.base_line_color(palette);
+
+        frame.stroke(&path, self.line_stroke(line_color, self.line_width));
 
+        // Add a subtle glow effect
         frame.stroke(
             &path,
-            canvas::Stroke::default()
-                .with_color(line_color)
-                .with_width(self.line_width),
+            self.line_stroke(line_color.scale_alpha(0.3), self.line_width + 2.0),
         );
+    }
 
-        // Add a subtle glow effect
+    /// Stroke for the line and its shadow and glow, with the configured cap and join
+    fn line_stroke(&self, color: Color, width: f32) -> canvas::Stroke<'static> {
+        canvas::Stroke {
+            line_cap: self.line_cap,
+            line_join: self.line_join,
+            ..canvas::Stroke::default()
+                .with_color(color)
+                .with_width(width)
+        }
+    }
+
+    /// Index ranges of the line between gap-mode zeros and [`Self::max_gap`] breaks
+    fn line_runs(&self, layout: &LineLayout) -> Vec<std::ops::Range<usize>> {
+        let runs = if self.zero_style == ZeroStyle::Gap {
+            Self::gap_runs(&layout.values)
+        } else {
+            std::iter::once(0..layout.values.len()).collect()
+        };
+        runs.into_iter()
+            .flat_map(|run| {
+                let mut start = run.start;
+                let mut split = Vec::new();
+                for &at in layout
+                    .breaks
+                    .iter()
+                    .filter(|&&at| at > run.start && at < run.end)
+                {
+                    split.push(start..at);
+                    start = at;
+                }
+                split.push(start..run.end);
+                split
+            })
+            .collect()
+    }
+
+    /// Index ranges of the line between zero or missing values
+    fn gap_runs(values: &[f64]) -> Vec<std::ops::Range<usize>> {
+        let mut runs = Vec::new();
+        let mut start = 0;
+        for (i, value) in values.iter().enumerate() {
+            if *value == 0.0 || !value.is_finite() {
+                if i > start {
+                    runs.push(start..i);
+                }
+                start = i + 1;
+            }
+        }
+        if values.len() > start {
+            runs.push(start..values.len());
+        }
+        runs
+    }
+
+    /// Indices of the run of values identical to the one at `index`
+    fn flat_run(values: &[f64], index: usize) -> std::ops::Range<usize> {
+        let value = values[index];
+        let start = values[..index]
+            .iter()
+            .rposition(|v| *v != value)
+            .map_or(0, |i| i + 1);
+        let end = values[index..]
+            .iter()
+            .position(|v| *v != value)
+            .map_or(values.len(), |i| index + i);
+        start..end
+    }
+
+    /// Ring marker used for zero values in [`ZeroStyle::Hollow`]
+    fn draw_hollow_point(
+        &self,
+        frame: &mut canvas::Frame,
+        point: Point,
+        radius: f32,
+        color: Color,
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let circle = canvas::Path::circle(point, radius);
+        frame.fill(&circle, palette.background.base.color);
         frame.stroke(
-            &path,
-            canvas::Stroke::default()
-                .with_color(line_color.scale_alpha(0.3))
-                .with_width(self.line_width + 2.0),
+            &circle,
+            canvas::Stroke::default().with_color(color).with_width(2.0),
         );
     }
 
-    #[allow(clippy::too_many_arguments)]
     fn draw_points(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        entries: &[(usize, Instant)],
+        theme: &Theme,
+    ) {
+        let (min_value, max_value, average) = (layout.min_value, layout.max_value, layout.average);
+        let mut last_drawn: Option<Point> = None;
+        for (i, (point, value)) in layout.points.iter().zip(&layout.values).enumerate() {
+            if self.min_point_gap > 0.0
+                && last_drawn.is_some_and(|last| last.distance(*point) < self.min_point_gap)
+            {
+                continue;
+            }
+            if self.collapse_flats
+                && i > 0
+                && layout.values.get(i - 1) == Some(value)
+                && layout.values.get(i + 1) == Some(value)
+            {
+                continue;
+            }
+            let radius = self.radius_for(*value, min_value, max_value)
+                * self.entry_scale(entries, layout.indices[i]);
+            self.draw_point(frame, *point, *value, i, radius, average, theme);
+            last_drawn = Some(*point);
+        }
+    }
+
+    /// Points at the local minima and maxima only, with value labels above peaks
+    /// and below troughs
+    fn draw_extrema(
         &self,
         frame: &mut canvas::Frame,
         points: &[Point],
         values: &[f64],
-        global_indices: &[usize],
-        state: &Option<usize>,
         average: f64,
         theme: &Theme,
     ) {
-        for (i, (point, value)) in points.iter().zip(values.iter()).enumerate() {
-            let is_hovered = *state == Some(i);
-
-            // Use the point color scheme to determine color
-            let point_color = {
-                let params = PointColorParams {
-                    index: i,
-                    value: *value,
-                    average,
-                    theme,
-                };
-                self.point_color_scheme.call(&params)
-            };
+        let text_color = self.text_color(theme.extended_palette());
+        for (i, is_max) in Self::extrema(values) {
+            self.draw_point(
+                frame,
+                points[i],
+                values[i],
+                i,
+                self.point_radius,
+                average,
+                theme,
+            );
+            if self.show_labels {
+                let offset = self.point_radius + 4.0;
+                frame.fill_text(canvas::Text {
+                    content: self.labels.format_tooltip(values[i]),
+                    position: Point::new(
+                        points[i].x,
+                        if is_max {
+                            points[i].y - offset
+                        } else {
+                            points[i].y + offset
+                        },
+                    ),
+                    color: text_color,
+                    size: Pixels(9.0),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_x: Center.into(),
+                    align_y: if is_max { Bottom } else { Top },
+                    ..canvas::Text::default()
+                });
+            }
+        }
+    }
+
+    /// Vertical line through the series index hovered in a graph sharing this one's view
+    fn draw_crosshair(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        index: usize,
+        theme: &Theme,
+    ) {
+        let Some(i) = layout.indices.iter().position(|&p| p == index) else {
+            return;
+        };
+        frame.fill_rectangle(
+            Point::new(layout.points[i].x, layout.top),
+            Size::new(1.0, layout.chart_height),
+            self.text_color(theme.extended_palette()).scale_alpha(0.35),
+        );
+    }
+
+    /// Leading edge line and ring on the latest sample while it is in view, and a
+    /// "LIVE" badge above the plot that dims when panned away from the latest data
+    fn draw_live_indicator(&self, frame: &mut canvas::Frame, layout: &LineLayout, theme: &Theme) {
+        let live_color = Color::from_rgb(0.95, 0.25, 0.25);
+        let following = layout.window.end == layout.total;
+
+        if following && let Some(latest) = layout.points.last() {
+            frame.fill_rectangle(
+                Point::new(latest.x, layout.top),
+                Size::new(1.0, layout.chart_height),
+                live_color.scale_alpha(0.4),
+            );
+            frame.stroke(
+                &canvas::Path::circle(*latest, self.point_radius + 3.0),
+                canvas::Stroke::default()
+                    .with_color(live_color)
+                    .with_width(1.5),
+            );
+        }
+
+        let alpha = if following { 1.0 } else { 0.4 };
+        let badge_y = layout.top - 10.0;
+        frame.fill(
+            &canvas::Path::circle(Point::new(layout.left + 4.0, badge_y), 3.5),
+            live_color.scale_alpha(alpha),
+        );
+        frame.fill_text(canvas::Text {
+            content: "LIVE".to_string(),
+            position: Point::new(layout.left + 11.0, badge_y),
+            color: self
+                .text_color(theme.extended_palette())
+                .scale_alpha(0.8 * alpha),
+            size: Pixels(10.0),
+            font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
+            align_y: Center.into(),
+            ..canvas::Text::default()
+        });
+    }
+
+    /// Value callouts above the first and last finite visible points, kept inside the canvas
+    fn draw_endpoint_labels(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        size: Size,
+        palette: &iced::theme::palette::Extended,
+    ) {
+        let finite = || {
+            layout
+                .points
+                .iter()
+                .zip(&layout.values)
+                .filter(|(_, v)| v.is_finite())
+        };
+        let (Some(first), Some(last)) = (finite().next(), finite().next_back()) else {
+            return;
+        };
+        let endpoints = if std::ptr::eq(first.0, last.0) {
+            vec![first]
+        } else {
+            vec![first, last]
+        };
 
-            let base_radius = self.point_radius;
-            let radius = if is_hovered {
-                base_radius + 3.0
+        let text_size = 10.0;
+        let border = self.base_line_color(palette);
+        for (point, value) in endpoints {
+            let content = self.labels.format_tooltip(*value);
+            let label = tooltip_size(&self.tooltip_style, &content, text_size);
+            let gap = self.point_radius + 4.0;
+            let x = point.x.clamp(
+                label.width / 2.0,
+                (size.width - label.width / 2.0).max(label.width / 2.0),
+            );
+            // Flip below the point when there is no room above
+            let y = if point.y - gap - label.height >= 0.0 {
+                point.y - gap
             } else {
-                base_radius
+                (point.y + gap + label.height).min(size.height)
             };
+            draw_tooltip(
+                frame,
+                &self.tooltip_style,
+                Point::new(x, y),
+                content,
+                text_size,
+                border,
+                text_shaping(self.advanced_text),
+            );
+        }
+    }
 
-            // Draw point shadow
-            frame.fill(
-                &canvas::Path::circle(Point::new(point.x + 1.0, point.y + 1.0), radius),
-                Color::BLACK.scale_alpha(0.3),
+    /// Interior local extrema as (index, is maximum). A plateau counts once, at
+    /// its first point.
+    fn extrema(values: &[f64]) -> Vec<(usize, bool)> {
+        (1..values.len())
+            .filter(|&i| values[i] != values[i - 1])
+            .filter_map(|i| {
+                let (prev, value) = (values[i - 1], values[i]);
+                // Look past the plateau so a step up or down isn't mistaken for a peak
+                let next = *values[i + 1..].iter().find(|v| **v != value)?;
+                if value > prev && value > next {
+                    Some((i, true))
+                } else if value < prev && value < next {
+                    Some((i, false))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Enlarged markers and tooltips for the pinned points in view and the hovered
+    /// point, drawn over the static layer. Overlapping tooltips stack upwards.
+    fn draw_point_tooltips(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        state: &LineGraphState,
+        theme: &Theme,
+    ) {
+        let mut indices: Vec<usize> = state
+            .pinned_points
+            .iter()
+            .filter(|p| layout.window.contains(p))
+            .filter_map(|p| layout.indices.iter().position(|i| i == p))
+            .chain(state.hovered_point)
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let text_size = 11.0;
+        let mut placed: Vec<Rectangle> = Vec::new();
+        for index in indices {
+            let (Some(point), Some(value)) = (layout.points.get(index), layout.values.get(index))
+            else {
+                continue;
+            };
+            let radius = self.radius_for(*value, layout.min_value, layout.max_value) + 3.0;
+            let point_color =
+                self.draw_point(frame, *point, *value, index, radius, layout.average, theme);
+
+            let run = if self.collapse_flats {
+                Self::flat_run(&layout.values, index)
+            } else {
+                index..index + 1
+            };
+            let x = if run.len() > 1 {
+                format!(
+                    "{}–{}",
+                    layout.indices[run.start],
+                    layout.indices[run.end - 1]
+                )
+            } else {
+                layout.indices[index].to_string()
+            };
+            let mut content = format!("x {x} • {}", self.labels.format_tooltip(*value));
+            if self.hover_show_deviation
+                && let Some(deviation) = format_deviation(*value, layout.average)
+            {
+                content = format!("{content} ({deviation})");
+            }
+            let size = tooltip_size(&self.tooltip_style, &content, text_size);
+            let mut anchor = Point::new(point.x, point.y - radius - 8.0);
+            let rect = |anchor: Point| Rectangle {
+                x: anchor.x - size.width / 2.0,
+                y: anchor.y - size.height,
+                width: size.width,
+                height: size.height,
+            };
+            while let Some(other) = placed.iter().find(|r| r.intersects(&rect(anchor))) {
+                anchor.y = other.y - 2.0;
+            }
+            placed.push(rect(anchor));
+
+            draw_tooltip(
+                frame,
+                &self.tooltip_style,
+                anchor,
+                content,
+                text_size,
+                point_color,
+                text_shaping(self.advanced_text),
             );
+        }
+    }
 
-            // Draw outer ring for depth
+    /// Draw a single point and return its color
+    #[allow(clippy::too_many_arguments)]
+    fn draw_point(
+        &self,
+        frame: &mut canvas::Frame,
+        point: Point,
+        value: f64,
+        index: usize,
+        radius: f32,
+        average: f64,
+        theme: &Theme,
+    ) -> Color {
+        // Use the point color scheme to determine color
+        let point_color = self.highlighted(
+            value,
+            self.point_color_scheme.call(&PointColorParams {
+                index,
+                value,
+                average,
+                theme,
+            }),
+        );
+        // Draw point shadow
+        if self.shadow.enabled {
             frame.fill(
-                &canvas::Path::circle(*point, radius + 1.0),
-                Color::WHITE.scale_alpha(0.8),
+                &canvas::Path::circle(point + self.shadow.offset, radius),
+                Color::BLACK.scale_alpha((self.shadow.alpha * 1.5).min(1.0)),
             );
+        }
+
+        // Draw outer ring for depth
+        frame.fill(
+            &canvas::Path::circle(point, radius + 1.0),
+            Color::WHITE.scale_alpha(0.8),
+        );
 
+        if self.zero_style == ZeroStyle::Hollow && value == 0.0 {
+            self.draw_hollow_point(frame, point, radius, point_color, theme.extended_palette());
+        } else {
             // Draw main point
-            frame.fill(&canvas::Path::circle(*point, radius), point_color);
+            frame.fill(&canvas::Path::circle(point, radius), point_color);
 
             // Add highlight to make it look more 3D
             frame.fill(
@@ -893,50 +2935,9 @@ where
                 ),
                 Color::WHITE.scale_alpha(0.6),
             );
-
-            // Show enhanced tooltip on hover
-            if is_hovered {
-                let tooltip_bg = Color::from_rgba(0.1, 0.1, 0.1, 0.9);
-                let tooltip_text = Color::WHITE;
-                let tooltip_width = 80.0;
-                let tooltip_height = 25.0;
-                let tooltip_x = point.x - tooltip_width / 2.0;
-                let tooltip_y = point.y - radius - tooltip_height - 8.0;
-
-                // Tooltip background with rounded corners effect
-                frame.fill(
-                    &canvas::Path::rectangle(
-                        Point::new(tooltip_x, tooltip_y),
-                        Size::new(tooltip_width, tooltip_height),
-                    ),
-                    tooltip_bg,
-                );
-
-                // Tooltip border
-                frame.stroke(
-                    &canvas::Path::rectangle(
-                        Point::new(tooltip_x, tooltip_y),
-                        Size::new(tooltip_width, tooltip_height),
-                    ),
-                    canvas::Stroke::default()
-                        .with_color(point_color)
-                        .with_width(1.5),
-                );
-
-                let global_x = *global_indices.get(i).unwrap_or(&i);
-                let tooltip_text_value = self.labels.format_tooltip(*value);
-                frame.fill_text(canvas::Text {
-                    content: format!("x {} • {}", global_x, tooltip_text_value),
-                    position: Point::new(point.x, tooltip_y + tooltip_height / 2.0),
-                    color: tooltip_text,
-                    size: Pixels(11.0),
-                    font: Font::MONOSPACE,
-                    align_x: Center.into(),
-                    align_y: Center.into(),
-                    ..canvas::Text::default()
-                });
-            }
         }
+
+        point_color
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -944,60 +2945,49 @@ where
         &self,
         frame: &mut canvas::Frame,
         bounds: Size,
-        padding: f32,
+        top: f32,
+        left: f32,
         chart_width: f32,
         chart_height: f32,
         min_value: f64,
-        max_value: f64,
         average: f64,
         value_range: f64,
+        y_ticks: &[(f32, f64)],
         palette: &iced::theme::palette::Extended,
         zoom: Zoom,
         visible_datapoints: &[(usize, f64)],
     ) {
-        let text_color = palette.background.base.text;
-        let average_line_color = Color::from_rgb(1.0, 0.6, 0.2); // Orange color for average line
+        let text_color = self.text_color(palette);
+        let average_line_color = self.average_color.unwrap_or(Color::from_rgb(1.0, 0.6, 0.2)); // Orange color for average line
 
         // Enhanced average line with better visibility
         let range_den: f64 = if value_range == 0.0 { 1.0 } else { value_range };
         let normalized_avg = (average - min_value) / range_den;
-        let avg_y = padding + chart_height - (normalized_avg as f32 * chart_height);
-
-        // Draw a more prominent average line with solid segments and glow effect
-        let segment_length = 15.0;
-        let gap_length = 5.0;
-        let total_width = chart_width;
-        let num_segments = (total_width / (segment_length + gap_length)) as usize;
-
-        // First draw a glow effect behind the average line
-        for i in 0..num_segments {
-            let start_x = padding + (i as f32) * (segment_length + gap_length);
-            let end_x = (start_x + segment_length).min(padding + chart_width);
-
-            // Glow effect
-            frame.stroke(
-                &canvas::Path::line(Point::new(start_x, avg_y), Point::new(end_x, avg_y)),
-                canvas::Stroke::default()
-                    .with_color(average_line_color.scale_alpha(0.3))
-                    .with_width(6.0),
+        let mut avg_y = top + chart_height - (normalized_avg as f32 * chart_height);
+        if self.clamp_average {
+            avg_y = clamp_reference_line(
+                frame,
+                left + chart_width - 6.0,
+                avg_y,
+                top,
+                top + chart_height,
+                average_line_color,
             );
         }
 
-        // Then draw the main average line segments
-        for i in 0..num_segments {
-            let start_x = padding + (i as f32) * (segment_length + gap_length);
-            let end_x = (start_x + segment_length).min(padding + chart_width);
-
-            frame.stroke(
-                &canvas::Path::line(Point::new(start_x, avg_y), Point::new(end_x, avg_y)),
-                canvas::Stroke::default()
-                    .with_color(average_line_color)
-                    .with_width(3.0),
-            );
-        }
+        // Draw a more prominent average line with a glow effect
+        draw_dashed_average(
+            frame,
+            left,
+            chart_width,
+            avg_y,
+            average_line_color,
+            self.average_dash,
+            self.average_glow,
+        );
 
         // Enhanced average label positioned on the right but above the line
-        let avg_label_x = padding + chart_width - 90.0;
+        let avg_label_x = left + chart_width - 90.0;
         let avg_label_width = 85.0;
         let avg_label_height = 24.0;
         let avg_label_y = avg_y - avg_label_height - 8.0; // Position above the line
@@ -1035,23 +3025,25 @@ where
                 avg_label_x + avg_label_width / 2.0,
                 avg_label_y + avg_label_height / 2.0,
             ),
-            color: Color::WHITE,
+            color: self.average_label_color.unwrap_or(Color::WHITE),
             size: Pixels(12.0),
             font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
             align_x: Center.into(),
             align_y: Center.into(),
             ..canvas::Text::default()
         });
 
         // Enhanced Y-axis labels with better formatting
-        for i in 0..=5 {
-            let y = padding + (i as f32 / 5.0) * chart_height;
-            let value = max_value - (i as f64 / 5.0) * value_range;
-
+        for &(y, value) in y_ticks {
             // Y-axis label background for better readability
-            let label_bg_width = 35.0;
+            let label_bg_width = if self.auto_padding {
+                left - LABEL_GUTTER
+            } else {
+                35.0
+            };
             let label_bg_height = 16.0;
-            let label_bg_x = padding - label_bg_width - 2.0;
+            let label_bg_x = left - label_bg_width - 2.0;
             let label_bg_y = y - label_bg_height / 2.0;
 
             frame.fill(
@@ -1064,14 +3056,36 @@ where
 
             frame.fill_text(canvas::Text {
                 content: self.labels.format_y_axis(value),
-                position: Point::new(padding - 5.0, y),
+                position: Point::new(left - 5.0, y),
                 color: text_color,
-                size: Pixels(9.0),
+                size: Pixels(Y_LABEL_SIZE),
                 font: Font::MONOSPACE,
+                shaping: text_shaping(self.advanced_text),
                 align_x: Right.into(),
                 align_y: Center.into(),
                 ..canvas::Text::default()
             });
+
+            if self.right_axis_labels {
+                let right = left + chart_width;
+                frame.fill(
+                    &canvas::Path::rectangle(
+                        Point::new(right + 2.0, label_bg_y),
+                        Size::new(label_bg_width, label_bg_height),
+                    ),
+                    palette.background.base.color.scale_alpha(0.8),
+                );
+                frame.fill_text(canvas::Text {
+                    content: self.labels.format_y_axis(value),
+                    position: Point::new(right + 5.0, y),
+                    color: text_color,
+                    size: Pixels(Y_LABEL_SIZE),
+                    font: Font::MONOSPACE,
+                    shaping: text_shaping(self.advanced_text),
+                    align_y: Center.into(),
+                    ..canvas::Text::default()
+                });
+            }
         }
 
         // Enhanced title with zoom level information
@@ -1080,6 +3094,10 @@ where
         let title_bg_x = (bounds.width - title_bg_width) / 2.0;
         let title_bg_y = 5.0;
 
+        if !self.show_title {
+            return;
+        }
+
         if let Some(title_text) = self.labels.format_title(zoom) {
             // Title background
             frame.fill(
@@ -1096,6 +3114,7 @@ where
                 color: text_color,
                 size: Pixels(16.0),
                 font: Font::MONOSPACE,
+                shaping: text_shaping(self.advanced_text),
                 align_x: Center.into(),
                 align_y: Center.into(),
                 ..canvas::Text::default()
@@ -1118,6 +3137,7 @@ where
             color: text_color.scale_alpha(0.7),
             size: Pixels(10.0),
             font: Font::MONOSPACE,
+            shaping: text_shaping(self.advanced_text),
             align_x: Center.into(),
             ..canvas::Text::default()
         });
@@ -0,0 +1,268 @@
+//! Overview strip under a line graph: the whole series with a draggable window
+//! over the zoomed region
+
+use super::{DecimationMode, Interaction, LineGraph, layout::LineLayout, state::LineGraphState};
+use crate::{utils::ValueMapper, zoom::Zoom};
+use iced::{Event, Point, Rectangle, Size, Theme, mouse, widget::canvas};
+
+/// Height of the overview strip, in pixels
+pub const OVERVIEW_HEIGHT: f32 = 28.0;
+
+/// Gap between the plot and the overview strip, in pixels
+pub const OVERVIEW_GAP: f32 = 6.0;
+
+/// How far from a window edge, in pixels, a press grabs the edge instead of the window
+const EDGE_GRAB: f32 = 5.0;
+
+/// What an overview drag is moving
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum OverviewDrag {
+    /// The whole window, held this many samples after its start
+    Move { grab: usize },
+    /// The start edge, with the end fixed
+    Start { end: usize },
+    /// The end edge, with the start fixed
+    End { start: usize },
+}
+
+impl<'a, I, T, M> LineGraph<'a, I, T, M>
+where
+    I: Iterator<Item = T> + Clone + 'a,
+    M: ValueMapper<T>,
+{
+    /// The full series as a min/max sparkline, with the visible window highlighted
+    pub(super) fn draw_overview(
+        &self,
+        frame: &mut canvas::Frame,
+        layout: &LineLayout,
+        theme: &Theme,
+    ) {
+        let Some(strip) = layout.overview else {
+            return;
+        };
+        let palette = theme.extended_palette();
+        let text = self.text_color(palette);
+
+        frame.fill_rectangle(strip.position(), strip.size(), text.scale_alpha(0.05));
+
+        let values: Vec<f64> = self
+            .datapoints
+            .clone()
+            .map(|v| self.mapper.map(&v))
+            .collect();
+        let kept: Vec<(usize, f64)> = DecimationMode::MinMaxPerColumn
+            .decimate(&values, strip.width as usize)
+            .unwrap_or_else(|| values.iter().copied().enumerate().collect());
+        let (min, max) = kept
+            .iter()
+            .filter(|(_, v)| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &(_, v)| {
+                (lo.min(v), hi.max(v))
+            });
+        let range = if max > min { max - min } else { 1.0 };
+        let last = values.len().saturating_sub(1).max(1) as f32;
+
+        if kept.len() > 1 && min.is_finite() {
+            let sparkline = canvas::Path::new(|builder| {
+                for (n, &(i, value)) in kept.iter().enumerate() {
+                    let point = Point::new(
+                        strip.x + i as f32 / last * strip.width,
+                        strip.y + strip.height
+                            - 2.0
+                            - ((value - min) / range) as f32 * (strip.height - 4.0),
+                    );
+                    if n == 0 {
+                        builder.move_to(point);
+                    } else {
+                        builder.line_to(point);
+                    }
+                }
+            });
+            frame.stroke(
+                &sparkline,
+                canvas::Stroke::default()
+                    .with_color(self.base_line_color(palette).scale_alpha(0.6))
+                    .with_width(1.0),
+            );
+        }
+
+        let window = overview_window(strip, layout.window.start, layout.window.end, layout.total);
+        frame.fill_rectangle(window.position(), window.size(), text.scale_alpha(0.12));
+        frame.stroke(
+            &canvas::Path::rectangle(window.position(), window.size()),
+            canvas::Stroke::default()
+                .with_color(text.scale_alpha(0.5))
+                .with_width(1.0),
+        );
+    }
+
+    /// Start, continue or end a drag on the overview window. `None` when the event
+    /// is not an overview drag, so normal handling can take it.
+    pub(super) fn handle_overview_event(
+        &self,
+        state: &mut LineGraphState,
+        event: &Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<canvas::Action<Interaction>> {
+        if !self.overview || self.external_zoom.is_some() {
+            return None;
+        }
+
+        match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                let position = cursor.position_in(bounds)?;
+                let layout = self.compute_layout(bounds.size(), state)?;
+                let strip = layout.overview.filter(|strip| strip.contains(position))?;
+                let window =
+                    overview_window(strip, layout.window.start, layout.window.end, layout.total);
+
+                let drag = if (position.x - window.x).abs() <= EDGE_GRAB {
+                    OverviewDrag::Start {
+                        end: layout.window.end,
+                    }
+                } else if (position.x - (window.x + window.width)).abs() <= EDGE_GRAB {
+                    OverviewDrag::End {
+                        start: layout.window.start,
+                    }
+                } else if window.contains(position) {
+                    let index = overview_index(strip, position.x, layout.total);
+                    OverviewDrag::Move {
+                        grab: index.saturating_sub(layout.window.start),
+                    }
+                } else {
+                    // Outside the window: jump it to center on the press
+                    OverviewDrag::Move {
+                        grab: layout.window.len() / 2,
+                    }
+                };
+                state.overview_drag = Some(drag);
+                Some(self.capture(self.drag_overview(
+                    state,
+                    strip,
+                    position.x,
+                    layout.window.len(),
+                )))
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                state.overview_drag?;
+                let position = cursor.position_from(bounds.position())?;
+                let layout = self.compute_layout(bounds.size(), state)?;
+                let strip = layout.overview?;
+                Some(self.capture(self.drag_overview(
+                    state,
+                    strip,
+                    position.x,
+                    layout.window.len(),
+                )))
+            }
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left)) => {
+                state.overview_drag.take()?;
+                Some(self.capture(canvas::Action::request_redraw()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Apply the current drag with the cursor at `x`, publishing a zoom change if
+    /// resizing changed the zoom
+    fn drag_overview(
+        &self,
+        state: &mut LineGraphState,
+        strip: Rectangle,
+        x: f32,
+        visible: usize,
+    ) -> canvas::Action<Interaction> {
+        let total = self.len;
+        let index = overview_index(strip, x, total);
+        let previous_zoom = state.zoom;
+
+        match state.overview_drag {
+            Some(OverviewDrag::Move { grab }) => {
+                let start = index
+                    .saturating_sub(grab)
+                    .min(total.saturating_sub(visible));
+                state.pan_absolute(start);
+            }
+            Some(OverviewDrag::Start { end }) => {
+                let start = index.min(end.saturating_sub(2));
+                state.zoom = self.zoom_for_count(end - start, total);
+                state.pan_absolute(start);
+            }
+            Some(OverviewDrag::End { start }) => {
+                let end = index.max(start + 2).min(total);
+                state.zoom = self.zoom_for_count(end.saturating_sub(start), total);
+                state.pan_absolute(start);
+            }
+            None => {}
+        }
+
+        state.hovered_point = None;
+        state.overlay.clear();
+        self.cache.clear();
+        if state.zoom != previous_zoom {
+            canvas::Action::publish(Interaction::ZoomChanged(state.zoom))
+        } else {
+            canvas::Action::request_redraw()
+        }
+    }
+
+    /// The zoom that shows about `count` of `total` samples, within the zoom limits
+    fn zoom_for_count(&self, count: usize, total: usize) -> Zoom {
+        if count >= total {
+            return Zoom::Full;
+        }
+        let zoom = self.base_points / count.max(1) as f32;
+        Zoom::Value(zoom.clamp(self.zoom_min, self.zoom_max))
+    }
+
+    /// Resize arrows over the window's edges and a grab hand over the window
+    pub(super) fn overview_interaction(
+        &self,
+        state: &LineGraphState,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+    ) -> Option<mouse::Interaction> {
+        if !self.overview || self.external_zoom.is_some() {
+            return None;
+        }
+        match state.overview_drag {
+            Some(OverviewDrag::Move { .. }) => return Some(mouse::Interaction::Grabbing),
+            Some(_) => return Some(mouse::Interaction::ResizingHorizontally),
+            None => {}
+        }
+
+        let position = cursor.position_in(bounds)?;
+        let layout = self.compute_layout(bounds.size(), state)?;
+        let strip = layout.overview.filter(|strip| strip.contains(position))?;
+        let window = overview_window(strip, layout.window.start, layout.window.end, layout.total);
+        if (position.x - window.x).abs() <= EDGE_GRAB
+            || (position.x - (window.x + window.width)).abs() <= EDGE_GRAB
+        {
+            Some(mouse::Interaction::ResizingHorizontally)
+        } else if window.contains(position) {
+            Some(mouse::Interaction::Grab)
+        } else {
+            Some(mouse::Interaction::Pointer)
+        }
+    }
+}
+
+/// The part of the strip covering samples `start..end` of `total`
+fn overview_window(strip: Rectangle, start: usize, end: usize, total: usize) -> Rectangle {
+    let total = total.max(1) as f32;
+    let x = strip.x + start as f32 / total * strip.width;
+    Rectangle::new(
+        Point::new(x, strip.y),
+        Size::new(
+            ((end - start) as f32 / total * strip.width).max(2.0),
+            strip.height,
+        ),
+    )
+}
+
+/// The sample under `x` on the strip
+fn overview_index(strip: Rectangle, x: f32, total: usize) -> usize {
+    let fraction = ((x - strip.x) / strip.width).clamp(0.0, 1.0);
+    (fraction * total as f32).round() as usize
+}
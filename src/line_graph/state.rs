@@ -1,15 +1,29 @@
 //! State management for line graphs
 
-use crate::{utils::ZoomableGraphState, zoom::Zoom};
+use std::{
+    cell::{Cell, RefCell},
+    ops::Range,
+    rc::Rc,
+};
 
-#[derive(Debug, Clone)]
+use iced::time::Instant;
+
+use super::overview::OverviewDrag;
+use crate::{
+    utils::{OverlayCache, ZoomableGraphState},
+    zoom::Zoom,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PanMode {
     Start,
     End,
     Absolute(usize),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Pan {
     pub mode: PanMode,
 }
@@ -22,11 +36,41 @@ impl Default for Pan {
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LineGraphState {
     pub zoom: Zoom,
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub hovered_point: Option<usize>,
     pub pan: Pan,         // logical pan mode
+    pub pan_seeded: bool, // false until the pan is first set; the graph's anchor applies until then
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub shift_down: bool, // track Shift for pan-only scroll
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub ctrl_down: bool, // track Ctrl/Cmd for ScrollBehavior::ZoomWithCtrl
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub focused: bool, // set by clicking the graph; enables arrow-key navigation
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub scroll_accumulator: f32, // wheel lines not yet turned into a zoom step
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) cdf_sorted: RefCell<Option<(u64, Rc<[f64]>)>>, // fingerprint of the series and its sorted finite values, for the CDF
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub seen_view_generation: Cell<u64>, // last SharedView change this graph has drawn
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) seen_shared_hover: Cell<Option<usize>>, // SharedView crosshair this graph has drawn
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) overlay: OverlayCache, // hover layer, redrawn without touching the main cache
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub pinned_points: Vec<usize>, // series indices whose tooltips stay open; see `LineGraph::pin_on_click`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) overview_drag: Option<OverviewDrag>, // drag in progress on the overview strip
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) reported_window: Option<Range<usize>>, // last window published as WindowChanged
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) settling_window: Option<(Range<usize>, Instant)>, // window to publish once unchanged until then
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) seen_len: Option<usize>, // series length at the last update, to spot appended points
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) point_entries: Vec<(usize, Instant)>, // series indices still animating in, and when they arrived
 }
 
 impl LineGraphState {
@@ -35,23 +79,90 @@ impl LineGraphState {
             zoom: initial_zoom,
             hovered_point: None,
             pan: Pan::default(),
+            pan_seeded: false,
             shift_down: false,
+            ctrl_down: false,
+            focused: false,
+            scroll_accumulator: 0.0,
+            cdf_sorted: RefCell::new(None),
+            seen_view_generation: Cell::new(0),
+            seen_shared_hover: Cell::new(None),
+            overlay: OverlayCache::default(),
+            pinned_points: Vec::new(),
+            overview_drag: None,
+            reported_window: None,
+            settling_window: None,
+            seen_len: None,
+            point_entries: Vec::new(),
         }
     }
 
     pub fn pan_start(&mut self) {
         self.pan.mode = PanMode::Start;
+        self.pan_seeded = true;
     }
 
     pub fn pan_end(&mut self) {
         self.pan.mode = PanMode::End;
+        self.pan_seeded = true;
     }
 
     pub fn pan_absolute(&mut self, start: usize) {
         self.pan.mode = PanMode::Absolute(start);
+        self.pan_seeded = true;
+    }
+
+    /// Zoom and pan as a short URL-safe token, e.g. "2.5~a120", for deep links
+    pub fn view_token(&self) -> String {
+        encode_view(self.zoom, &self.pan)
+    }
+
+    /// Restore a view saved with [`LineGraphState::view_token`] or
+    /// [`SharedView::view_token`]; `None` if the token is malformed
+    pub fn from_view_token(token: &str) -> Option<Self> {
+        let (zoom, pan) = decode_view(token)?;
+        let mut state = Self::new(zoom);
+        state.pan = pan;
+        state.pan_seeded = true;
+        Some(state)
     }
 }
 
+/// `<zoom>~<pan>`: zoom is `f` for full view or the factor; pan is `s`, `e` or
+/// `a<start>`. Only digits, letters, `.`, `-` and `~`, so it needs no escaping in URLs.
+fn encode_view(zoom: Zoom, pan: &Pan) -> String {
+    let zoom = match zoom {
+        Zoom::Full => "f".to_string(),
+        Zoom::Value(value) => value.to_string(),
+    };
+    let pan = match pan.mode {
+        PanMode::Start => "s".to_string(),
+        PanMode::End => "e".to_string(),
+        PanMode::Absolute(start) => format!("a{start}"),
+    };
+    format!("{zoom}~{pan}")
+}
+
+fn decode_view(token: &str) -> Option<(Zoom, Pan)> {
+    let (zoom, pan) = token.split_once('~')?;
+    let zoom = match zoom {
+        "f" => Zoom::Full,
+        // Not Zoom::new: its floor would lose zooms below it that the zoom limits allow
+        value => Zoom::Value(
+            value
+                .parse::<f32>()
+                .ok()
+                .filter(|v| v.is_finite() && *v > 0.0)?,
+        ),
+    };
+    let mode = match pan {
+        "s" => PanMode::Start,
+        "e" => PanMode::End,
+        start => PanMode::Absolute(start.strip_prefix('a')?.parse().ok()?),
+    };
+    Some((zoom, Pan { mode }))
+}
+
 impl ZoomableGraphState for LineGraphState {
     fn zoom(&self) -> Zoom {
         self.zoom
@@ -61,3 +172,101 @@ impl ZoomableGraphState for LineGraphState {
         self.zoom = zoom;
     }
 }
+
+/// Zoom and pan shared by several line graphs so they zoom and pan together.
+///
+/// Clone the handle into each graph via [`LineGraph::shared_view`](super::LineGraph::shared_view);
+/// scrolling over any of them updates the shared view and the others redraw to match.
+#[derive(Debug, Clone, Default)]
+pub struct SharedView(Rc<RefCell<SharedViewInner>>);
+
+#[derive(Debug, Default)]
+struct SharedViewInner {
+    zoom: Zoom,
+    pan: Pan,
+    generation: u64,
+    hover: Option<usize>,
+    plot: Option<SharedPlot>,
+}
+
+/// Where the most recently drawn graph put its plot, for a shared X axis
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedPlot {
+    /// Indices of the visible datapoints within the full series
+    pub window: Range<usize>,
+    /// Left edge of the plot
+    pub left: f32,
+    pub chart_width: f32,
+}
+
+impl SharedView {
+    pub fn new(zoom: Zoom) -> Self {
+        Self(Rc::new(RefCell::new(SharedViewInner {
+            zoom,
+            ..SharedViewInner::default()
+        })))
+    }
+
+    pub fn zoom(&self) -> Zoom {
+        self.0.borrow().zoom
+    }
+
+    pub fn pan(&self) -> Pan {
+        self.0.borrow().pan.clone()
+    }
+
+    /// Update the view, e.g. from app-level controls
+    pub fn set(&self, zoom: Zoom, pan: Pan) {
+        let mut inner = self.0.borrow_mut();
+        if inner.zoom != zoom || inner.pan != pan {
+            inner.zoom = zoom;
+            inner.pan = pan;
+            inner.generation += 1;
+        }
+    }
+
+    /// Zoom and pan as a short URL-safe token; see [`LineGraphState::view_token`]
+    pub fn view_token(&self) -> String {
+        let inner = self.0.borrow();
+        encode_view(inner.zoom, &inner.pan)
+    }
+
+    /// A shared view restored from a token; `None` if the token is malformed
+    pub fn from_view_token(token: &str) -> Option<Self> {
+        let (zoom, pan) = decode_view(token)?;
+        let view = Self::new(zoom);
+        view.set(zoom, pan);
+        Some(view)
+    }
+
+    /// Series index hovered in any graph sharing this view, drawn as a crosshair in all of them
+    pub fn hover(&self) -> Option<usize> {
+        self.0.borrow().hover
+    }
+
+    pub(crate) fn set_hover(&self, hover: Option<usize>) {
+        self.0.borrow_mut().hover = hover;
+    }
+
+    /// The visible window and plot extent last drawn by a graph on this view
+    pub fn plot(&self) -> Option<SharedPlot> {
+        self.0.borrow().plot.clone()
+    }
+
+    pub(crate) fn record_plot(&self, plot: SharedPlot) {
+        self.0.borrow_mut().plot = Some(plot);
+    }
+
+    /// Bumped on every change, so graphs can tell their cached drawing is stale
+    pub fn generation(&self) -> u64 {
+        self.0.borrow().generation
+    }
+
+    /// Copy the shared zoom and pan into a graph's own state
+    pub(crate) fn load_into(&self, state: &mut LineGraphState) {
+        let inner = self.0.borrow();
+        state.zoom = inner.zoom;
+        state.pan = inner.pan.clone();
+        state.pan_seeded = true;
+    }
+}
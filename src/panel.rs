@@ -0,0 +1,146 @@
+//! Several line graphs stacked in rows that zoom, pan and hover together
+
+use iced::{
+    Center, Element, Font, Length, Pixels, Point, Rectangle, Renderer, Size, Theme, Top, mouse,
+    widget::{Column, canvas},
+};
+
+use crate::{
+    chart::Chart,
+    line_graph::{LineGraph, state::SharedView},
+    utils::{LineInteraction, ValueMapper, nice_ticks},
+};
+
+/// Height of the shared X axis row, in pixels
+const X_AXIS_HEIGHT: f32 = 20.0;
+
+/// Line graphs stacked vertically on one [`SharedView`], e.g. latency, throughput
+/// and errors in an observability panel.
+///
+/// Every row zooms and pans with the others, hovering a point in one draws a
+/// crosshair at the same index in all of them, and a single X axis is drawn
+/// under the last row.
+#[allow(missing_debug_implementations)]
+pub struct StackedPanel<'a, Message> {
+    view: SharedView,
+    rows: Vec<Element<'a, Message>>,
+    x_axis: bool,
+    spacing: f32,
+}
+
+impl<'a, Message: 'a> StackedPanel<'a, Message> {
+    pub fn new(view: &SharedView) -> Self {
+        Self {
+            view: view.clone(),
+            rows: Vec::new(),
+            x_axis: true,
+            spacing: 0.0,
+        }
+    }
+
+    /// Add a graph as the next row, attached to the panel's view. Its interactions are dropped.
+    pub fn push<I, T, M>(self, graph: LineGraph<'a, I, T, M>) -> Self
+    where
+        I: Iterator<Item = T> + Clone + 'a,
+        T: 'a,
+        M: ValueMapper<T> + 'a,
+    {
+        self.push_chart(Chart::new(graph))
+    }
+
+    /// Add a chart as the next row, keeping its message handlers
+    pub fn push_chart<I, T, M>(
+        mut self,
+        chart: Chart<'a, LineGraph<'a, I, T, M>, LineInteraction, Message>,
+    ) -> Self
+    where
+        I: Iterator<Item = T> + Clone + 'a,
+        T: 'a,
+        M: ValueMapper<T> + 'a,
+    {
+        let view = self.view.clone();
+        self.rows
+            .push(chart.graph(|graph| graph.shared_view(&view)).into());
+        self
+    }
+
+    /// Draw the shared X axis under the last row (default true)
+    pub fn x_axis(mut self, show: bool) -> Self {
+        self.x_axis = show;
+        self
+    }
+
+    /// Vertical space between rows
+    pub fn spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+}
+
+impl<'a, Message: 'a> From<StackedPanel<'a, Message>> for Element<'a, Message> {
+    fn from(panel: StackedPanel<'a, Message>) -> Self {
+        let mut column = Column::with_children(panel.rows).spacing(panel.spacing);
+        if panel.x_axis {
+            column = column.push(
+                canvas(SharedXAxis { view: panel.view })
+                    .width(Length::Fill)
+                    .height(Length::Fixed(X_AXIS_HEIGHT)),
+            );
+        }
+        column.into()
+    }
+}
+
+/// Sample index ticks for the window last drawn on a [`SharedView`]. Assumes the
+/// rows use a linear X axis with edge-to-edge placement, the default.
+struct SharedXAxis {
+    view: SharedView,
+}
+
+impl<Message> canvas::Program<Message> for SharedXAxis {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let Some(plot) = self.view.plot().filter(|plot| !plot.window.is_empty()) else {
+            return vec![frame.into_geometry()];
+        };
+
+        let (first, last) = (plot.window.start as f64, (plot.window.end - 1) as f64);
+        let span = (last - first).max(1.0);
+        let target = (plot.chart_width / 80.0).max(2.0) as usize;
+        let color = theme
+            .extended_palette()
+            .background
+            .base
+            .text
+            .scale_alpha(0.6);
+
+        for tick in nice_ticks(first, last, target) {
+            if tick < first || tick > last || tick.fract() != 0.0 {
+                continue;
+            }
+            let x = plot.left + ((tick - first) / span) as f32 * plot.chart_width;
+            frame.fill_rectangle(Point::new(x, 0.0), Size::new(1.0, 4.0), color);
+            frame.fill_text(canvas::Text {
+                content: format!("{tick}"),
+                position: Point::new(x, 6.0),
+                color,
+                size: Pixels(10.0),
+                font: Font::MONOSPACE,
+                align_x: Center.into(),
+                align_y: Top,
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
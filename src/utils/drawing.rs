@@ -1,6 +1,62 @@
 //! Shared drawing utilities for all graph types
 
-use iced::{Color, Font, Pixels, Point, Size, Theme, widget::canvas};
+use iced::{
+    Center, Color, Font, Padding, Pixels, Point, Size, Theme, Vector, widget::canvas,
+    widget::text::Shaping,
+};
+
+/// Which grid line directions to draw
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridAxes {
+    #[default]
+    Both,
+    /// Horizontal lines only (value references)
+    Horizontal,
+    /// Vertical lines only
+    Vertical,
+    /// No grid lines; the chart border is still drawn
+    None,
+}
+
+impl GridAxes {
+    pub fn horizontal(self) -> bool {
+        matches!(self, GridAxes::Both | GridAxes::Horizontal)
+    }
+
+    pub fn vertical(self) -> bool {
+        matches!(self, GridAxes::Both | GridAxes::Vertical)
+    }
+}
+
+/// Whether gridlines are drawn under or over the data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridLayer {
+    #[default]
+    Behind,
+    /// Over the data at higher contrast, for an outlined look
+    Front,
+}
+
+/// Corner of the canvas, e.g. for a [`draw_watermark`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+/// Shaping for graph labels and tooltips: full shaping with font fallback when
+/// `advanced`, for CJK, emoji or other complex scripts in custom labels. Costlier
+/// than the default, which only shapes text it detects as non-ASCII.
+pub fn text_shaping(advanced: bool) -> Shaping {
+    if advanced {
+        Shaping::Advanced
+    } else {
+        Shaping::default()
+    }
+}
 
 /// Common grid drawing functionality
 pub struct GridConfig {
@@ -8,9 +64,17 @@ pub struct GridConfig {
     pub chart_width: f32,
     pub chart_height: f32,
     pub horizontal_lines: usize,
+    /// Explicit y positions for horizontal lines; overrides `horizontal_lines` when non-empty
+    pub horizontal_positions: Vec<f32>,
     pub vertical_lines: usize,
+    pub axes: GridAxes,
     pub major_alpha: f32,
     pub minor_alpha: f32,
+    /// Base color for the lines and border; the theme's text color when `None`
+    pub color: Option<Color>,
+    /// Fainter lines evenly splitting each gap between neighboring gridlines into
+    /// this many extra parts, for a graph-paper look (0 for none)
+    pub minor_subdivisions: usize,
 }
 
 impl Default for GridConfig {
@@ -20,46 +84,261 @@ impl Default for GridConfig {
             chart_width: 0.0,
             chart_height: 0.0,
             horizontal_lines: 10,
+            horizontal_positions: Vec::new(),
             vertical_lines: 10,
+            axes: GridAxes::Both,
             major_alpha: 0.15,
             minor_alpha: 0.05,
+            color: None,
+            minor_subdivisions: 0,
+        }
+    }
+}
+
+/// Styling for the hover tooltip box
+#[derive(Debug, Clone, Copy)]
+pub struct TooltipStyle {
+    pub background: Color,
+    pub text_color: Color,
+    pub border_width: f32,
+    /// Space between the text and the box edges; the box is sized to fit the content
+    pub padding: Padding,
+    pub corner_radius: f32,
+}
+
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::from_rgba(0.1, 0.1, 0.1, 0.9),
+            text_color: Color::WHITE,
+            border_width: 1.5,
+            padding: Padding::from([7.0, 6.0]),
+            corner_radius: 0.0,
+        }
+    }
+}
+
+/// Drop shadow drawn behind lines and point markers
+#[derive(Debug, Clone, Copy)]
+pub struct ShadowConfig {
+    pub offset: Vector,
+    /// Opacity of the line shadow; point markers use 1.5× this for their smaller footprint
+    pub alpha: f32,
+    pub enabled: bool,
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            offset: Vector::new(1.0, 1.0),
+            alpha: 0.2,
+            enabled: true,
         }
     }
 }
 
+/// Approximate rendered width of monospace text
+pub fn estimate_text_width(content: &str, size: f32) -> f32 {
+    content.chars().count() as f32 * size * 0.6
+}
+
+/// Size of the box [`draw_tooltip`] draws for `content`
+pub fn tooltip_size(style: &TooltipStyle, content: &str, text_size: f32) -> Size {
+    Size::new(
+        estimate_text_width(content, text_size) + style.padding.left + style.padding.right,
+        text_size + style.padding.top + style.padding.bottom,
+    )
+}
+
+/// Draw a tooltip box centered horizontally on `anchor`, with its bottom edge at `anchor.y`
+pub fn draw_tooltip(
+    frame: &mut canvas::Frame,
+    style: &TooltipStyle,
+    anchor: Point,
+    content: String,
+    text_size: f32,
+    border_color: Color,
+    shaping: Shaping,
+) {
+    let Size { width, height } = tooltip_size(style, &content, text_size);
+    let top_left = Point::new(anchor.x - width / 2.0, anchor.y - height);
+    let path = canvas::Path::rounded_rectangle(
+        top_left,
+        Size::new(width, height),
+        style.corner_radius.into(),
+    );
+
+    frame.fill(&path, style.background);
+
+    if style.border_width > 0.0 {
+        frame.stroke(
+            &path,
+            canvas::Stroke::default()
+                .with_color(border_color)
+                .with_width(style.border_width),
+        );
+    }
+
+    frame.fill_text(canvas::Text {
+        content,
+        position: Point::new(anchor.x, top_left.y + height / 2.0),
+        color: style.text_color,
+        size: Pixels(text_size),
+        font: Font::MONOSPACE,
+        shaping,
+        align_x: Center.into(),
+        align_y: Center.into(),
+        ..canvas::Text::default()
+    });
+}
+
+/// Small faint text tucked into a corner of the canvas, in the padding outside the plot
+pub fn draw_watermark(
+    frame: &mut canvas::Frame,
+    content: &str,
+    corner: Corner,
+    color: Color,
+    shaping: Shaping,
+) {
+    const INSET: f32 = 6.0;
+    let size = frame.size();
+    let (x, align_x) = match corner {
+        Corner::TopLeft | Corner::BottomLeft => (INSET, iced::alignment::Horizontal::Left),
+        Corner::TopRight | Corner::BottomRight => {
+            (size.width - INSET, iced::alignment::Horizontal::Right)
+        }
+    };
+    let (y, align_y) = match corner {
+        Corner::TopLeft | Corner::TopRight => (INSET, iced::alignment::Vertical::Top),
+        Corner::BottomLeft | Corner::BottomRight => {
+            (size.height - INSET, iced::alignment::Vertical::Bottom)
+        }
+    };
+    frame.fill_text(canvas::Text {
+        content: content.to_string(),
+        position: Point::new(x, y),
+        color,
+        size: Pixels(10.0),
+        font: Font::MONOSPACE,
+        shaping,
+        align_x: align_x.into(),
+        align_y,
+        ..canvas::Text::default()
+    });
+}
+
+/// Dim everything drawn so far and center a "Loading…" message over it
+pub fn draw_loading_overlay(
+    frame: &mut canvas::Frame,
+    palette: &iced::theme::palette::Extended,
+    text_color: Color,
+) {
+    let size = frame.size();
+    frame.fill_rectangle(
+        Point::ORIGIN,
+        size,
+        palette.background.base.color.scale_alpha(0.7),
+    );
+    frame.fill_text(canvas::Text {
+        content: "Loading…".to_string(),
+        position: Point::new(size.width / 2.0, size.height / 2.0),
+        color: text_color.scale_alpha(0.8),
+        size: Pixels(14.0),
+        font: Font::MONOSPACE,
+        align_x: Center.into(),
+        align_y: Center.into(),
+        ..canvas::Text::default()
+    });
+}
+
 /// Draw a standard grid with major and minor lines
 pub fn draw_grid(
     frame: &mut canvas::Frame,
     config: &GridConfig,
     palette: &iced::theme::palette::Extended,
 ) {
-    let major_grid_color = palette.background.base.text.scale_alpha(config.major_alpha);
-    let minor_grid_color = palette.background.base.text.scale_alpha(config.minor_alpha);
+    let base_color = config.color.unwrap_or(palette.background.base.text);
+    let major_grid_color = base_color.scale_alpha(config.major_alpha);
+    let minor_grid_color = base_color.scale_alpha(config.minor_alpha);
+
+    let subdivision_color = base_color.scale_alpha(config.minor_alpha * 0.6);
+    let subdivision_stroke = canvas::Stroke::default()
+        .with_color(subdivision_color)
+        .with_width(0.3);
 
     // Draw horizontal grid lines
-    for i in 0..=config.horizontal_lines {
-        let y = config.padding + (i as f32 / config.horizontal_lines as f32) * config.chart_height;
-        let color = if i % 2 == 0 {
-            major_grid_color
-        } else {
-            minor_grid_color
-        };
-        let width = if i % 2 == 0 { 0.8 } else { 0.4 };
+    let horizontal_positions: Vec<f32> = if config.horizontal_positions.is_empty() {
+        (0..=config.horizontal_lines)
+            .map(|i| {
+                config.padding + (i as f32 / config.horizontal_lines as f32) * config.chart_height
+            })
+            .collect()
+    } else {
+        config.horizontal_positions.clone()
+    };
+    if config.axes.horizontal() {
+        for y in subdivisions(&horizontal_positions, config.minor_subdivisions) {
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(config.padding, y),
+                    Point::new(config.padding + config.chart_width, y),
+                ),
+                subdivision_stroke,
+            );
+        }
+    }
+    if config.axes.horizontal() && config.horizontal_positions.is_empty() {
+        for (i, &y) in horizontal_positions.iter().enumerate() {
+            let color = if i % 2 == 0 {
+                major_grid_color
+            } else {
+                minor_grid_color
+            };
+            let width = if i % 2 == 0 { 0.8 } else { 0.4 };
 
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(config.padding, y),
+                    Point::new(config.padding + config.chart_width, y),
+                ),
+                canvas::Stroke::default()
+                    .with_color(color)
+                    .with_width(width),
+            );
+        }
+    } else if config.axes.horizontal() {
+        for &y in &config.horizontal_positions {
+            frame.stroke(
+                &canvas::Path::line(
+                    Point::new(config.padding, y),
+                    Point::new(config.padding + config.chart_width, y),
+                ),
+                canvas::Stroke::default()
+                    .with_color(major_grid_color)
+                    .with_width(0.8),
+            );
+        }
+    }
+
+    // Draw vertical grid lines
+    let vertical_lines = if config.axes.vertical() {
+        config.vertical_lines + 1
+    } else {
+        0
+    };
+    let vertical_positions: Vec<f32> = (0..vertical_lines)
+        .map(|i| config.padding + (i as f32 / config.vertical_lines as f32) * config.chart_width)
+        .collect();
+    for x in subdivisions(&vertical_positions, config.minor_subdivisions) {
         frame.stroke(
             &canvas::Path::line(
-                Point::new(config.padding, y),
-                Point::new(config.padding + config.chart_width, y),
+                Point::new(x, config.padding),
+                Point::new(x, config.padding + config.chart_height),
             ),
-            canvas::Stroke::default()
-                .with_color(color)
-                .with_width(width),
+            subdivision_stroke,
         );
     }
-
-    // Draw vertical grid lines
-    for i in 0..=config.vertical_lines {
-        let x = config.padding + (i as f32 / config.vertical_lines as f32) * config.chart_width;
+    for (i, &x) in vertical_positions.iter().enumerate() {
         let color = if i % 2 == 0 {
             major_grid_color
         } else {
@@ -79,7 +358,7 @@ pub fn draw_grid(
     }
 
     // Draw chart border
-    let border_color = palette.background.base.text.scale_alpha(0.3);
+    let border_color = base_color.scale_alpha(0.3);
     frame.stroke(
         &canvas::Path::rectangle(
             Point::new(config.padding, config.padding),
@@ -91,6 +370,15 @@ pub fn draw_grid(
     );
 }
 
+/// Positions splitting each gap between consecutive `lines` into `parts + 1` even
+/// pieces, excluding the lines themselves
+pub(crate) fn subdivisions(lines: &[f32], parts: usize) -> impl Iterator<Item = f32> + '_ {
+    lines.windows(2).flat_map(move |pair| {
+        let step = (pair[1] - pair[0]) / (parts + 1) as f32;
+        (1..=parts).map(move |k| pair[0] + step * k as f32)
+    })
+}
+
 /// Draw value labels on the Y-axis
 #[allow(clippy::too_many_arguments)]
 pub fn draw_y_axis_labels(
@@ -142,6 +430,57 @@ pub fn draw_y_axis_labels(
     }
 }
 
+/// Dash lengths for dashed reference lines such as the average line
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DashPattern {
+    pub segment: f32,
+    /// Space between segments; zero or less draws a solid line
+    pub gap: f32,
+}
+
+/// Color of median reference lines, distinct from the average's
+pub const MEDIAN_COLOR: Color = Color::from_rgb(0.7, 0.4, 1.0);
+
+/// Dash of median reference lines, shorter than the average's default
+pub const MEDIAN_DASH: DashPattern = DashPattern {
+    segment: 6.0,
+    gap: 4.0,
+};
+
+impl DashPattern {
+    pub const SOLID: Self = Self {
+        segment: f32::INFINITY,
+        gap: 0.0,
+    };
+
+    pub fn new(segment: f32, gap: f32) -> Self {
+        Self {
+            segment: segment.max(1.0),
+            gap,
+        }
+    }
+
+    /// Start and end x of each segment between `start` and `end`; the last one is cut short
+    pub fn segments(self, start: f32, end: f32) -> Vec<(f32, f32)> {
+        if self.gap <= 0.0 || !self.segment.is_finite() {
+            return vec![(start, end)];
+        }
+        let mut segments = Vec::new();
+        let mut x = start;
+        while x < end {
+            segments.push((x, (x + self.segment).min(end)));
+            x += self.segment + self.gap;
+        }
+        segments
+    }
+}
+
+impl Default for DashPattern {
+    fn default() -> Self {
+        Self::new(15.0, 5.0)
+    }
+}
+
 /// Draw an average line across the chart
 #[allow(clippy::too_many_arguments)]
 pub fn draw_average_line(
@@ -162,31 +501,70 @@ pub fn draw_average_line(
     let normalized_avg = (average - min_value) / value_range;
     let avg_y = padding + chart_height - (normalized_avg as f32 * chart_height);
 
-    // Draw dashed line
-    let segment_length = 15.0;
-    let gap_length = 5.0;
-    let total_width = chart_width;
-    let num_segments = (total_width / (segment_length + gap_length)) as usize;
+    draw_dashed_average(
+        frame,
+        padding,
+        chart_width,
+        avg_y,
+        color,
+        DashPattern::default(),
+        1.0,
+    );
+}
+
+/// Clamp a reference line's `y` into `top..=bottom`. When it had to move, draw a
+/// small arrow at `x` pointing the way the real value lies. Returns the y to draw at.
+pub fn clamp_reference_line(
+    frame: &mut canvas::Frame,
+    x: f32,
+    y: f32,
+    top: f32,
+    bottom: f32,
+    color: Color,
+) -> f32 {
+    let clamped = y.clamp(top, bottom.max(top));
+    if clamped != y {
+        // Tip on the edge, body inside the plot
+        let direction = if y < top { -1.0 } else { 1.0 };
+        let base = clamped - direction * 8.0;
+        let arrow = canvas::Path::new(|builder| {
+            builder.move_to(Point::new(x, clamped));
+            builder.line_to(Point::new(x - 5.0, base));
+            builder.line_to(Point::new(x + 5.0, base));
+            builder.close();
+        });
+        frame.fill(&arrow, color);
+    }
+    clamped
+}
 
-    // Glow effect behind the average line
-    for i in 0..num_segments {
-        let start_x = padding + (i as f32) * (segment_length + gap_length);
-        let end_x = (start_x + segment_length).min(padding + chart_width);
+/// Draw the average line at `avg_y`, dashed by `dash`, with a glow behind it scaled
+/// by `glow` (1.0 as drawn by default, 0.0 for none)
+pub fn draw_dashed_average(
+    frame: &mut canvas::Frame,
+    padding: f32,
+    chart_width: f32,
+    avg_y: f32,
+    color: Color,
+    dash: DashPattern,
+    glow: f32,
+) {
+    let segments = dash.segments(padding, padding + chart_width);
 
-        // Glow effect
-        frame.stroke(
-            &canvas::Path::line(Point::new(start_x, avg_y), Point::new(end_x, avg_y)),
-            canvas::Stroke::default()
-                .with_color(color.scale_alpha(0.3))
-                .with_width(6.0),
-        );
+    // Glow effect behind the average line, skipped entirely when disabled
+    if glow > 0.0 {
+        for &(start_x, end_x) in &segments {
+            frame.stroke(
+                &canvas::Path::line(Point::new(start_x, avg_y), Point::new(end_x, avg_y)),
+                canvas::Stroke::default()
+                    .with_color(color.scale_alpha((0.3 * glow).min(1.0)))
+                    .with_width(6.0),
+            );
+        }
     }
 
     // Main average line segments
-    for i in 0..num_segments {
-        let start_x = padding + (i as f32) * (segment_length + gap_length);
-        let end_x = (start_x + segment_length).min(padding + chart_width);
-
+    for &(start_x, end_x) in &segments {
         frame.stroke(
             &canvas::Path::line(Point::new(start_x, avg_y), Point::new(end_x, avg_y)),
             canvas::Stroke::default().with_color(color).with_width(3.0),
@@ -1,5 +1,7 @@
 //! Shared graph state functionality
 
+use iced::widget::canvas;
+
 use crate::zoom::Zoom;
 
 /// Common trait for graph state that supports zoom
@@ -33,3 +35,22 @@ pub fn calculate_visible_range(total_items: usize, zoom: Zoom, base_items: f32)
 
     (start_index, visible_count)
 }
+
+/// Second cache kept in graph state for the hover layer, so hovering redraws only
+/// the marker and tooltip instead of the caller's cache. Cloning yields an empty cache.
+#[derive(Debug, Default)]
+pub(crate) struct OverlayCache(canvas::Cache);
+
+impl Clone for OverlayCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl std::ops::Deref for OverlayCache {
+    type Target = canvas::Cache;
+
+    fn deref(&self) -> &canvas::Cache {
+        &self.0
+    }
+}
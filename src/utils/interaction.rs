@@ -1,5 +1,9 @@
 //! Shared interaction types for all graph types
 
+use std::ops::Range;
+
+use iced::Point;
+
 use crate::zoom::Zoom;
 
 /// Generic graph interaction events
@@ -11,6 +15,15 @@ pub enum GraphInteraction<T> {
     ItemClicked(usize),
     /// Zoom level changed
     ZoomChanged(Zoom),
+    /// The visible window of series indices settled after zooming or panning
+    WindowChanged(Range<usize>),
+    /// Right click, with the item and value under the cursor (if any) and the
+    /// cursor position in window coordinates
+    ContextMenu {
+        index: Option<usize>,
+        value: Option<f64>,
+        position: Point,
+    },
     /// Custom graph-specific interaction
     Custom(T),
 }
@@ -18,17 +31,42 @@ pub enum GraphInteraction<T> {
 /// Bar graph specific interaction types
 #[derive(Debug, Clone)]
 pub enum BarInteraction {
-    BarHovered(usize),
-    BarClicked(usize),
+    /// `bar` is the index among the visible bars; `samples` the original sample
+    /// indices it summarizes, `None` for histogram buckets, prebinned bars and
+    /// padding slots
+    BarHovered {
+        bar: usize,
+        samples: Option<Range<usize>>,
+    },
+    /// See [`BarInteraction::BarHovered`]
+    BarClicked {
+        bar: usize,
+        samples: Option<Range<usize>>,
+    },
     ZoomChanged(Zoom),
+    /// Right click; see [`GraphInteraction::ContextMenu`]
+    ContextMenu {
+        index: Option<usize>,
+        value: Option<f64>,
+        position: Point,
+    },
 }
 
 impl From<BarInteraction> for GraphInteraction<BarInteraction> {
     fn from(interaction: BarInteraction) -> Self {
         match interaction {
-            BarInteraction::BarHovered(index) => GraphInteraction::ItemHovered(index),
-            BarInteraction::BarClicked(index) => GraphInteraction::ItemClicked(index),
+            BarInteraction::BarHovered { bar, .. } => GraphInteraction::ItemHovered(bar),
+            BarInteraction::BarClicked { bar, .. } => GraphInteraction::ItemClicked(bar),
             BarInteraction::ZoomChanged(zoom) => GraphInteraction::ZoomChanged(zoom),
+            BarInteraction::ContextMenu {
+                index,
+                value,
+                position,
+            } => GraphInteraction::ContextMenu {
+                index,
+                value,
+                position,
+            },
         }
     }
 }
@@ -38,15 +76,40 @@ impl From<BarInteraction> for GraphInteraction<BarInteraction> {
 pub enum LineInteraction {
     PointHovered(usize),
     PointClicked(usize),
+    /// A point's tooltip was pinned by clicking it; the index is into the full series
+    PointPinned(usize),
+    /// A pinned tooltip was clicked again and unpinned; the index is into the full series
+    PointUnpinned(usize),
     ZoomChanged(Zoom),
+    /// See [`GraphInteraction::WindowChanged`]; only published with
+    /// [`LineGraph::report_window`](crate::line_graph::LineGraph::report_window)
+    WindowChanged(Range<usize>),
+    /// Right click; see [`GraphInteraction::ContextMenu`]
+    ContextMenu {
+        index: Option<usize>,
+        value: Option<f64>,
+        position: Point,
+    },
 }
 
 impl From<LineInteraction> for GraphInteraction<LineInteraction> {
     fn from(interaction: LineInteraction) -> Self {
         match interaction {
             LineInteraction::PointHovered(index) => GraphInteraction::ItemHovered(index),
-            LineInteraction::PointClicked(index) => GraphInteraction::ItemClicked(index),
+            LineInteraction::PointClicked(index)
+            | LineInteraction::PointPinned(index)
+            | LineInteraction::PointUnpinned(index) => GraphInteraction::ItemClicked(index),
             LineInteraction::ZoomChanged(zoom) => GraphInteraction::ZoomChanged(zoom),
+            LineInteraction::WindowChanged(window) => GraphInteraction::WindowChanged(window),
+            LineInteraction::ContextMenu {
+                index,
+                value,
+                position,
+            } => GraphInteraction::ContextMenu {
+                index,
+                value,
+                position,
+            },
         }
     }
 }
@@ -12,3 +12,75 @@ pub trait LabelFormatter: Send + Sync {
     fn format_subtitle(&self, zoom: Zoom, start_idx: usize, end_idx: usize, count: usize)
     -> String;
 }
+
+/// Format a millisecond value in whichever of µs, ms or s reads best.
+///
+/// Values scaled to µs or s keep at least one decimal, so 1200 reads "1.2s" rather than "1s".
+pub fn format_time_ms(value: f64, decimals: u8) -> String {
+    let magnitude = value.abs();
+    let (scaled, unit, decimals) = if magnitude >= 1000.0 {
+        (value / 1000.0, "s", decimals.max(1))
+    } else if magnitude > 0.0 && magnitude < 1.0 {
+        (value * 1000.0, "µs", decimals.max(1))
+    } else {
+        (value, "ms", decimals)
+    };
+    format!("{v:.prec$}{unit}", v = scaled, prec = decimals as usize)
+}
+
+/// Signed percentage of `value` away from `average`, e.g. "+18% vs avg", or `None`
+/// when the average is zero or not finite
+pub fn format_deviation(value: f64, average: f64) -> Option<String> {
+    if average == 0.0 || !average.is_finite() || !value.is_finite() {
+        return None;
+    }
+    let percent = (value - average) / average.abs() * 100.0;
+    Some(format!("{percent:+.0}% vs avg"))
+}
+
+/// Format a label value with `decimals` and `unit_suffix`; in µs/ms/s instead when
+/// `auto_time_unit`, or as a whole number when `integer_values`. Digits are then
+/// grouped with `thousands_separator`, if any.
+pub fn format_value(
+    value: f64,
+    decimals: u8,
+    unit_suffix: &str,
+    auto_time_unit: bool,
+    integer_values: bool,
+    thousands_separator: Option<char>,
+) -> String {
+    let text = if integer_values {
+        // Adding zero turns a rounded -0.4 into "0" rather than "-0"
+        format!("{:.0}{unit_suffix}", value.round() + 0.0)
+    } else if auto_time_unit {
+        format_time_ms(value, decimals)
+    } else {
+        format!("{value:.prec$}{unit_suffix}", prec = decimals as usize)
+    };
+    match thousands_separator {
+        Some(separator) => group_thousands(&text, separator),
+        None => text,
+    }
+}
+
+/// Insert `separator` between groups of three digits in the first run of digits,
+/// leaving any sign, decimals and unit suffix alone: "-12345.6ms" becomes "-12,345.6ms"
+pub fn group_thousands(text: &str, separator: char) -> String {
+    let Some(start) = text.find(|c: char| c.is_ascii_digit()) else {
+        return text.to_string();
+    };
+    let digits = text[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map_or(text.len(), |len| start + len);
+
+    let mut grouped = String::with_capacity(text.len() + (digits - start) / 3);
+    grouped.push_str(&text[..start]);
+    for (i, digit) in text[start..digits].chars().enumerate() {
+        if i > 0 && (digits - start - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped.push_str(&text[digits..]);
+    grouped
+}
@@ -5,9 +5,13 @@ pub mod graph_state;
 pub mod interaction;
 pub mod labels;
 pub mod mapper;
+pub mod stats;
+pub mod ticks;
 
 pub use drawing::*;
 pub use graph_state::*;
 pub use interaction::*;
 pub use labels::*;
 pub use mapper::*;
+pub use stats::*;
+pub use ticks::*;
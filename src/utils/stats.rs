@@ -0,0 +1,59 @@
+//! Summary statistics over samples
+
+/// Value at percentile `p` (0–100) of already sorted samples, interpolating
+/// linearly between neighbouring ranks
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Mean and population standard deviation of samples, or NaN for both if there
+/// are none. Deviations are taken from the mean in a second pass, which stays
+/// accurate for large values with a small spread such as epoch timestamps.
+pub fn mean_std_dev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance.sqrt())
+}
+
+/// Median of unsorted samples, or NaN if there are none
+pub fn median(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    percentile(&sorted, 50.0)
+}
+
+/// Min, quartiles and max of a set of samples, as drawn by a box plot
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FiveNumberSummary {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+}
+
+impl FiveNumberSummary {
+    /// Summarize the finite samples, or `None` if there are none
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        Some(Self {
+            min: sorted[0],
+            q1: percentile(&sorted, 25.0),
+            median: percentile(&sorted, 50.0),
+            q3: percentile(&sorted, 75.0),
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
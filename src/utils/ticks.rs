@@ -0,0 +1,63 @@
+//! Axis tick selection shared by all graph types
+
+/// Pick round tick values (1, 2 or 5 × 10^k steps) inside `[min, max]`.
+///
+/// `target` is the approximate number of ticks wanted; the result may hold a few
+/// more or fewer depending on where the round values land.
+pub fn nice_ticks(min: f64, max: f64, target: usize) -> Vec<f64> {
+    if !min.is_finite() || !max.is_finite() || max < min {
+        return Vec::new();
+    }
+    if max == min {
+        return vec![min];
+    }
+
+    let range = nice_number(max - min, false);
+    let step = nice_number(range / (target.max(2) - 1) as f64, true);
+    let first = (min / step).ceil() * step;
+    // Tolerate float drift so a tick sitting exactly on `max` is kept
+    let epsilon = step * 1e-9;
+
+    let mut ticks = Vec::new();
+    let mut i = 0;
+    loop {
+        let value = first + i as f64 * step;
+        if value > max + epsilon {
+            break;
+        }
+        // Snap values like 0.30000000000000004 and -0.0 to clean numbers
+        let value = (value / step).round() * step + 0.0;
+        ticks.push(value);
+        i += 1;
+    }
+    ticks
+}
+
+/// Round `value` to a "nice" number: 1, 2, 5 or 10 times a power of ten
+fn nice_number(value: f64, round: bool) -> f64 {
+    let exponent = value.log10().floor();
+    let magnitude = 10f64.powf(exponent);
+    let fraction = value / magnitude;
+
+    let nice = if round {
+        if fraction < 1.5 {
+            1.0
+        } else if fraction < 3.0 {
+            2.0
+        } else if fraction < 7.0 {
+            5.0
+        } else {
+            10.0
+        }
+    } else if fraction <= 1.0 {
+        1.0
+    } else if fraction <= 2.0 {
+        2.0
+    } else if fraction <= 5.0 {
+        5.0
+    } else {
+        10.0
+    };
+
+    nice * magnitude
+}
@@ -1,6 +1,11 @@
 //! Shared zoom functionality for all graph types
 
+/// Values this close above the minimum count as at the minimum, so float drift
+/// from repeated 0.1 steps doesn't leave a near-duplicate level
+const MIN_TOLERANCE: f32 = 1e-4;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Zoom {
     /// Specific zoom level with a numeric value
     Value(f32),
@@ -54,17 +59,13 @@ impl Zoom {
         match self {
             Zoom::Full => Zoom::Full, // Already at maximum zoom out
             Zoom::Value(value) => {
-                if value <= min {
+                if value <= min + MIN_TOLERANCE {
                     // If at or below minimum, go to full view
                     Zoom::Full
                 } else if value <= 1.0 {
-                    // When at 1x or below, decrement in 0.1 steps down to min
-                    let new_value = (value - 0.1).max(min);
-                    if new_value <= min {
-                        Zoom::Full
-                    } else {
-                        Zoom::Value(new_value)
-                    }
+                    // When at 1x or below, decrement in 0.1 steps down to min,
+                    // landing on min itself before the next step goes to full view
+                    Zoom::Value((value - 0.1).max(min))
                 } else {
                     // When zoomed in, decrement in 1.0 steps: max -> ... -> 2.0 -> 1.0
                     Zoom::Value((value - 1.0).max(1.0))
@@ -72,6 +73,25 @@ impl Zoom {
             }
         }
     }
+
+    /// Step in to the next whole level up to `max` (rounded down): full view and
+    /// anything below 1x go to 1x, and fractional levels snap to the next integer
+    pub fn increment_integer(self, max: f32) -> Self {
+        let max = max.floor().max(1.0);
+        match self {
+            Zoom::Full => Zoom::Value(1.0),
+            Zoom::Value(value) if value < 1.0 => Zoom::Value(1.0),
+            Zoom::Value(value) => Zoom::Value((value.floor() + 1.0).min(max)),
+        }
+    }
+
+    /// Step out to the previous whole level, going to full view from 1x or below
+    pub fn decrement_integer(self) -> Self {
+        match self {
+            Zoom::Value(value) if value > 1.0 => Zoom::Value((value.ceil() - 1.0).max(1.0)),
+            _ => Zoom::Full,
+        }
+    }
 }
 
 impl Default for Zoom {
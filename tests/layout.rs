@@ -0,0 +1,660 @@
+//! Geometry checks for both graphs via `compute_layout`, no renderer needed
+
+use std::time::Duration;
+
+use iced::{Color, Point, Rectangle, Size, keyboard, mouse, widget::canvas};
+use snowline::bar_graph::layout::BOTTOM_MARGIN;
+use snowline::bar_graph::{BinSpan, MIN_BAR_PIXELS};
+use snowline::line_graph::layout::{CHART_PADDING, HEADER_HEIGHT};
+use snowline::line_graph::{Anchor, DecimationMode, HoverMode, PointPlacement, Tiebreak, XScale};
+use snowline::prelude::*;
+use snowline::utils::{DefaultMap, mean_std_dev};
+
+const BOUNDS: Size = Size::new(800.0, 400.0);
+
+fn series(len: usize) -> Vec<f64> {
+    (0..len).map(|i| (i % 17) as f64).collect()
+}
+
+fn line_layout(data: &[f64], state: &LineGraphState) -> Option<LineLayout> {
+    let cache = canvas::Cache::new();
+    LineGraph::from_slice(data, &cache).compute_layout(BOUNDS, state)
+}
+
+#[test]
+fn line_empty_series_has_no_layout() {
+    assert_eq!(line_layout(&[], &LineGraphState::default()), None);
+}
+
+#[test]
+fn line_visible_count_follows_zoom() {
+    let data = series(1000);
+    // base_points defaults to 50
+    for (zoom, visible) in [
+        (Zoom::Full, 1000),
+        (Zoom::Value(0.1), 500),
+        (Zoom::Value(0.5), 100),
+        (Zoom::Value(1.0), 50),
+        (Zoom::Value(5.0), 10),
+        (Zoom::Value(100.0), 2),
+    ] {
+        let layout = line_layout(&data, &LineGraphState::new(zoom)).unwrap();
+        assert_eq!(layout.window.len(), visible, "zoom {zoom:?}");
+        assert_eq!(layout.points.len(), visible);
+        assert_eq!(layout.values.len(), visible);
+        assert_eq!(layout.total, 1000);
+    }
+}
+
+#[test]
+fn line_window_never_exceeds_short_series() {
+    for len in [1, 2, 10, 49] {
+        let data = series(len);
+        let layout = line_layout(&data, &LineGraphState::new(Zoom::Value(0.1))).unwrap();
+        assert_eq!(layout.window, 0..len);
+    }
+}
+
+#[test]
+fn line_window_follows_pan_mode() {
+    let data = series(1000);
+    let mut state = LineGraphState::new(Zoom::Value(1.0));
+
+    assert_eq!(line_layout(&data, &state).unwrap().window, 950..1000);
+
+    state.pan_start();
+    assert_eq!(line_layout(&data, &state).unwrap().window, 0..50);
+
+    state.pan_absolute(400);
+    assert_eq!(line_layout(&data, &state).unwrap().window, 400..450);
+
+    // Out of range starts clamp to the last full window
+    state.pan_absolute(5000);
+    assert_eq!(line_layout(&data, &state).unwrap().window, 950..1000);
+}
+
+#[test]
+fn line_points_span_the_chart_area() {
+    let data = series(100);
+    let layout = line_layout(&data, &LineGraphState::new(Zoom::Full)).unwrap();
+    let (left, right) = (CHART_PADDING, BOUNDS.width - CHART_PADDING);
+    // The title and subtitle sit above the plot, not over it
+    let (top, bottom) = (CHART_PADDING + HEADER_HEIGHT, BOUNDS.height - CHART_PADDING);
+
+    assert_eq!(layout.top, top);
+    assert_eq!(layout.points.first().unwrap().x, left);
+    assert!((layout.points.last().unwrap().x - right).abs() < 1e-3);
+    assert!(layout.points.windows(2).all(|p| p[0].x < p[1].x));
+
+    for (point, value) in layout.points.iter().zip(&layout.values) {
+        assert!(point.y >= top - 1e-3 && point.y <= bottom + 1e-3);
+        if *value == layout.min_value {
+            assert!((point.y - bottom).abs() < 1e-3);
+        }
+        if *value == layout.max_value {
+            assert!((point.y - top).abs() < 1e-3);
+        }
+    }
+}
+
+#[test]
+fn line_without_title_reclaims_the_header() {
+    let data = series(100);
+    let cache = canvas::Cache::new();
+    let state = LineGraphState::new(Zoom::Full);
+    let layout = |graph: LineGraph<'_, _, f64, _>| graph.compute_layout(BOUNDS, &state).unwrap();
+
+    let untitled = layout(LineGraph::from_slice(&data, &cache).show_title(false));
+    assert_eq!(untitled.top, CHART_PADDING);
+    assert_eq!(untitled.chart_height, BOUNDS.height - 2.0 * CHART_PADDING);
+
+    // Without labels there is no header to make room for either
+    let unlabelled = layout(LineGraph::from_slice(&data, &cache).show_labels(false));
+    assert_eq!(unlabelled.top, CHART_PADDING);
+}
+
+#[test]
+fn line_band_widens_the_range_and_brackets_the_line() {
+    let data = series(100);
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .bounds_fn(|v: &f64| (v - 5.0, v + 5.0))
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+
+    assert_eq!((layout.min_value, layout.max_value), (-5.0, 21.0));
+    assert_eq!(layout.band.len(), layout.points.len());
+    for ((low, high), point) in layout.band.iter().zip(&layout.points) {
+        assert_eq!((low.x, high.x), (point.x, point.x));
+        assert!(high.y < point.y && point.y < low.y);
+    }
+}
+
+#[test]
+fn line_min_max_decimation_keeps_spikes() {
+    let mut data = vec![0.0; 10_000];
+    data[4321] = 100.0;
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .decimation(DecimationMode::MinMaxPerColumn)
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+
+    assert!(layout.points.len() <= 2 * layout.chart_width as usize);
+    assert_eq!(layout.indices.len(), layout.points.len());
+    assert!(layout.indices.windows(2).all(|i| i[0] < i[1]));
+    let spike = layout.indices.iter().position(|&i| i == 4321).unwrap();
+    assert_eq!(layout.values[spike], 100.0);
+}
+
+#[test]
+fn line_auto_padding_fits_the_widest_label() {
+    let cache = canvas::Cache::new();
+    let state = LineGraphState::new(Zoom::Full);
+    let left_for = |data: &[f64]| {
+        let layout = LineGraph::from_slice(data, &cache)
+            .auto_padding(true)
+            .compute_layout(BOUNDS, &state)
+            .unwrap();
+        assert_eq!(layout.points.first().unwrap().x, layout.left);
+        assert!((layout.left + layout.chart_width + CHART_PADDING - BOUNDS.width).abs() < 1e-3);
+        layout.left
+    };
+
+    let small = left_for(&[1.0, 2.0, 5.0]);
+    let large = left_for(&[1.0, 20_000.0, 123_456.0]);
+    assert!(small < CHART_PADDING);
+    assert!(large > small);
+}
+
+#[test]
+fn line_inset_points_sit_at_slot_centers() {
+    let data = series(4);
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .point_placement(PointPlacement::Inset)
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+    let slot = layout.chart_width / 4.0;
+    for (i, point) in layout.points.iter().enumerate() {
+        assert!((point.x - (CHART_PADDING + slot * (i as f32 + 0.5))).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn line_log_x_spreads_the_start_of_the_series() {
+    let data = series(100);
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .x_scale(XScale::Log10)
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+    let xs: Vec<f32> = layout.points.iter().map(|p| p.x).collect();
+
+    assert_eq!(xs[0], layout.left);
+    assert!((xs[99] - (layout.left + layout.chart_width)).abs() < 1e-3);
+    // Index 9 (x = 10) sits halfway on a 1..100 log axis
+    assert!((xs[9] - (layout.left + layout.chart_width / 2.0)).abs() < 1e-3);
+    assert!(xs.windows(3).all(|w| w[1] - w[0] > w[2] - w[1]));
+}
+
+#[test]
+fn line_flat_series_sits_on_the_baseline() {
+    let data = vec![3.0; 20];
+    let layout = line_layout(&data, &LineGraphState::new(Zoom::Full)).unwrap();
+    assert_eq!(layout.average, 3.0);
+    assert!(
+        layout
+            .points
+            .iter()
+            .all(|p| (p.y - (BOUNDS.height - CHART_PADDING)).abs() < 1e-3)
+    );
+}
+
+#[test]
+fn line_hover_hits_the_drawn_point() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let graph = LineGraph::from_slice(&data, &cache);
+    let bounds = Rectangle::new(Point::ORIGIN, BOUNDS);
+
+    for zoom in [Zoom::Value(0.5), Zoom::Value(1.0), Zoom::Value(5.0)] {
+        let mut state = LineGraphState::new(zoom);
+        state.pan_absolute(123);
+        let layout = graph.compute_layout(BOUNDS, &state).unwrap();
+
+        for target in [0, layout.points.len() / 2, layout.points.len() - 1] {
+            let position = layout.points[target];
+            let event = iced::Event::Mouse(mouse::Event::CursorMoved { position });
+            let _ = canvas::Program::update(
+                &graph,
+                &mut state,
+                &event,
+                bounds,
+                mouse::Cursor::Available(position),
+            );
+            assert_eq!(state.hovered_point, Some(target), "zoom {zoom:?}");
+        }
+    }
+}
+
+fn bar_layout(graph: BarGraph<'_, impl Iterator<Item = f64> + Clone, f64>) -> BarLayout {
+    graph
+        .compute_layout(BOUNDS, &BarGraphState::default())
+        .unwrap()
+}
+
+#[test]
+fn bar_bins_cover_contiguous_samples() {
+    let data: Vec<f64> = (0..100).map(f64::from).collect();
+    let cache = canvas::Cache::new();
+    let layout = bar_layout(BarGraph::from_slice(&data, &cache).bins(10));
+
+    assert_eq!(layout.values.len(), 10);
+    assert_eq!(layout.bars.len(), 10);
+    assert_eq!(layout.total_samples, 100);
+    for (i, (value, span)) in layout.values.iter().zip(&layout.spans).enumerate() {
+        assert_eq!(*span, BinSpan::Samples(i * 10..i * 10 + 10));
+        // Average of i*10..i*10+10
+        assert_eq!(*value, i as f64 * 10.0 + 4.5);
+    }
+}
+
+#[test]
+fn bar_count_is_capped_by_data_and_width() {
+    let cache = canvas::Cache::new();
+    for (len, bins, expected) in [
+        (5, 50, 5),
+        (100, 50, 50),
+        (10_000, 10_000, (BOUNDS.width / MIN_BAR_PIXELS) as usize),
+    ] {
+        let data = series(len);
+        let graph = BarGraph::from_slice(&data, &cache).bins(bins);
+        assert_eq!(
+            graph.effective_bin_count(BOUNDS.width, &BarGraphState::default()),
+            expected
+        );
+        assert_eq!(bar_layout(graph).values.len(), expected);
+    }
+}
+
+#[test]
+fn bar_zoom_rebins_the_visible_window() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let graph = BarGraph::from_slice(&data, &cache).bins(50);
+
+    for (zoom, samples) in [
+        (Zoom::Full, 1000),
+        (Zoom::Value(1.0), 1000),
+        (Zoom::Value(2.0), 500),
+        (Zoom::Value(10.0), 100),
+        (Zoom::Value(40.0), 25),
+    ] {
+        let state = BarGraphState::new(zoom);
+        let layout = graph.compute_layout(BOUNDS, &state).unwrap();
+        let bins = samples.min(50);
+        assert_eq!(layout.total_samples, samples, "zoom {zoom:?}");
+        assert_eq!(layout.values.len(), bins, "zoom {zoom:?}");
+        assert_eq!(graph.effective_bin_count(BOUNDS.width, &state), bins);
+        // The window ends at the latest sample and spans keep absolute indices
+        let sample_range = |span: &BinSpan| match span {
+            BinSpan::Samples(range) => range.clone(),
+            BinSpan::Values(_) => unreachable!(),
+        };
+        assert_eq!(sample_range(&layout.spans[0]).start, 1000 - samples);
+        assert_eq!(sample_range(layout.spans.last().unwrap()).end, 1000);
+    }
+}
+
+#[test]
+fn bar_line_overlay_lines_up_with_bar_centers() {
+    let data = [1.0, 2.0, 4.0, 3.0];
+    let rates = [10.0f64, 40.0, 20.0, 30.0];
+    let cache = canvas::Cache::new();
+    let graph =
+        BarGraph::from_slice(&data, &cache).with_line_overlay(rates, Color::WHITE, DefaultMap);
+
+    let layout = bar_layout(graph);
+    assert_eq!(layout.line_overlay.len(), 4);
+    assert_eq!(layout.line_overlay_max, 40.0);
+    assert_eq!(layout.max_value, 4.0);
+    for (point, bar) in layout.line_overlay.iter().zip(&layout.bars) {
+        assert!((point.x - bar.center_x()).abs() < 1e-3);
+    }
+    // The line's maximum reaches the top on its own axis
+    assert!(layout.line_overlay[1].y.abs() < 1e-3);
+}
+
+#[test]
+fn bar_heights_scale_to_the_tallest_bar() {
+    let data = [1.0, 2.0, 4.0, 0.0];
+    let cache = canvas::Cache::new();
+    let layout = bar_layout(BarGraph::from_slice(&data, &cache));
+    let available = BOUNDS.height - BOTTOM_MARGIN;
+
+    assert_eq!(layout.max_value, 4.0);
+    assert!((layout.bars[2].height - available).abs() < 1e-3);
+    assert!((layout.bars[1].height - available / 2.0).abs() < 1e-3);
+    assert!((layout.bars[0].height - available / 4.0).abs() < 1e-3);
+    // Zero bars keep a sliver so they stay visible
+    assert_eq!(layout.bars[3].height, 3.0);
+    for bar in &layout.bars {
+        assert!((bar.y + bar.height - available).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn bar_all_zero_series_has_no_layout() {
+    let data = [0.0; 8];
+    let cache = canvas::Cache::new();
+    assert_eq!(
+        BarGraph::from_slice(&data, &cache).compute_layout(BOUNDS, &BarGraphState::default()),
+        None
+    );
+}
+
+#[test]
+fn bar_histogram_counts_every_sample() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let layout = bar_layout(BarGraph::from_slice(&data, &cache).histogram(8));
+
+    assert_eq!(layout.values.len(), 8);
+    assert_eq!(layout.values.iter().sum::<f64>(), 1000.0);
+    assert!(matches!(layout.spans.first(), Some(BinSpan::Values(r)) if r.start == 0.0));
+}
+
+#[test]
+fn data_extent_spans_the_full_series() {
+    let data = [3.0, -1.0, f64::NAN, 7.5, 2.0];
+    let cache = canvas::Cache::new();
+    let line = LineGraph::from_slice(&data, &cache);
+    assert_eq!(line.data_extent(), Some((-1.0, 7.5)));
+    assert_eq!(
+        BarGraph::from_slice(&data, &cache).data_extent(),
+        Some((-1.0, 7.5))
+    );
+    assert_eq!(
+        LineGraph::from_slice(&[] as &[f64], &cache).data_extent(),
+        None
+    );
+}
+
+#[test]
+fn line_anchor_seeds_the_initial_window() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let state = LineGraphState::new(Zoom::Value(1.0));
+    let graph = LineGraph::from_slice(&data, &cache).anchor(Anchor::Start);
+    assert_eq!(graph.compute_layout(BOUNDS, &state).unwrap().window, 0..50);
+
+    // An explicit pan on the state wins over the anchor
+    let mut state = state;
+    state.pan_end();
+    assert_eq!(
+        graph.compute_layout(BOUNDS, &state).unwrap().window,
+        950..1000
+    );
+}
+
+#[test]
+fn bar_prebinned_values_are_drawn_as_given() {
+    let bins: Vec<f64> = (1..=120).map(f64::from).collect();
+    let cache = canvas::Cache::new();
+    let graph = BarGraph::prebinned(bins.clone(), &cache);
+
+    assert_eq!(
+        graph.effective_bin_count(BOUNDS.width, &BarGraphState::default()),
+        120
+    );
+    let layout = bar_layout(graph);
+    assert_eq!(layout.values, bins);
+    assert!(layout.spans.is_empty());
+}
+
+#[test]
+fn bar_pad_bins_keeps_the_requested_slots() {
+    let data = [5.0, 3.0, 0.0];
+    let cache = canvas::Cache::new();
+    let graph = BarGraph::from_slice(&data, &cache).bins(10).pad_bins(true);
+
+    assert_eq!(
+        graph.effective_bin_count(BOUNDS.width, &BarGraphState::default()),
+        10
+    );
+    let layout = bar_layout(graph);
+    assert_eq!(layout.values.len(), 10);
+    // The real zero keeps its sliver, padding slots draw nothing
+    assert_eq!(layout.bars[2].height, 3.0);
+    assert!(layout.bars[3..].iter().all(|bar| bar.height == 0.0));
+    assert_eq!(layout.average, 8.0 / 3.0);
+}
+
+#[test]
+fn line_overview_sits_below_the_plot() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .overview(true)
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Value(1.0)))
+        .unwrap();
+
+    let strip = layout.overview.unwrap();
+    assert_eq!((strip.x, strip.width), (layout.left, layout.chart_width));
+    assert!(strip.y > layout.top + layout.chart_height);
+    assert!(strip.y + strip.height <= BOUNDS.height);
+    assert!(layout.points.iter().all(|p| p.y < strip.y));
+}
+
+#[test]
+fn line_right_axis_labels_reserve_the_left_padding() {
+    let data: Vec<f64> = (0..100).map(|i| i as f64 * 1e8).collect();
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::from_slice(&data, &cache)
+        .auto_padding(true)
+        .right_axis_labels(true)
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+
+    assert!(layout.left > CHART_PADDING);
+    let right = BOUNDS.width - layout.left - layout.chart_width;
+    assert_eq!(right, layout.left);
+}
+
+#[test]
+fn line_max_gap_breaks_at_long_pauses() {
+    // Seconds at which each sample was taken, with pauses after 3 and 7
+    let times = [0u64, 1, 2, 3, 20, 21, 22, 23, 60, 61];
+    let data: Vec<(u64, f64)> = times.iter().map(|&t| (t, t as f64)).collect();
+    let cache = canvas::Cache::new();
+    let layout = LineGraph::with_mapper(data.iter().copied(), &cache, |(_, v): &(u64, f64)| *v)
+        .max_gap(Duration::from_secs(5), |(t, _): &(u64, f64)| {
+            Duration::from_secs(*t)
+        })
+        .compute_layout(BOUNDS, &LineGraphState::new(Zoom::Full))
+        .unwrap();
+
+    assert_eq!(layout.breaks, vec![4, 8]);
+}
+
+#[test]
+fn line_std_dev_covers_the_visible_values() {
+    let data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+    let layout = line_layout(&data, &LineGraphState::new(Zoom::Full)).unwrap();
+
+    assert_eq!((layout.average, layout.std_dev), (5.0, 2.0));
+}
+
+#[test]
+fn bar_keys_stay_with_their_bins_when_zoomed() {
+    let bins: Vec<f64> = (1..=120).map(f64::from).collect();
+    let cache = canvas::Cache::new();
+    let layout = BarGraph::prebinned(bins, &cache)
+        .compute_layout(BOUNDS, &BarGraphState::new(Zoom::Value(2.0)))
+        .unwrap();
+    assert_eq!(layout.keys, (60..120).collect::<Vec<_>>());
+
+    let data: Vec<f64> = (0..100).map(f64::from).collect();
+    let layout = bar_layout(BarGraph::from_slice(&data, &cache).bins(10));
+    for (key, span) in layout.keys.iter().zip(&layout.spans) {
+        assert_eq!(*span, BinSpan::Samples(key * 10..key * 10 + 10));
+    }
+}
+
+#[test]
+fn line_nearest_x_breaks_ties_within_a_pixel_column() {
+    // 2000 points over ~720 pixels, so neighbours share columns
+    let data: Vec<f64> = (0..2000).map(|i| ((i * 7919) % 101) as f64).collect();
+    let cache = canvas::Cache::new();
+    let bounds = Rectangle::new(Point::ORIGIN, BOUNDS);
+    let state = LineGraphState::new(Zoom::Value(0.025));
+    let layout = line_layout(&data, &state).unwrap();
+    let column = |i: usize| layout.points[i].x.floor();
+    let first = (1..layout.points.len())
+        .find(|&i| column(i) == column(i - 1))
+        .unwrap()
+        - 1;
+    let tied: Vec<usize> = (first..layout.points.len())
+        .take_while(|&i| column(i) == column(first))
+        .collect();
+    let position = Point::new(layout.points[first].x, layout.top);
+
+    let hovered = |tiebreak| {
+        let graph = LineGraph::from_slice(&data, &cache).hover_mode(HoverMode::NearestX(tiebreak));
+        let mut state = state.clone();
+        let event = iced::Event::Mouse(mouse::Event::CursorMoved { position });
+        let _ = canvas::Program::update(
+            &graph,
+            &mut state,
+            &event,
+            bounds,
+            mouse::Cursor::Available(position),
+        );
+        state.hovered_point.unwrap()
+    };
+    let by_value = |i: &&usize| layout.values[**i];
+
+    assert_eq!(hovered(Tiebreak::Earliest), first);
+    assert_eq!(hovered(Tiebreak::Latest), *tied.last().unwrap());
+    let highest = tied
+        .iter()
+        .max_by(|a, b| by_value(a).total_cmp(&by_value(b)));
+    assert_eq!(hovered(Tiebreak::Highest), *highest.unwrap());
+    let lowest = tied
+        .iter()
+        .min_by(|a, b| by_value(a).total_cmp(&by_value(b)));
+    assert_eq!(hovered(Tiebreak::Lowest), *lowest.unwrap());
+}
+
+#[test]
+fn bar_deltas_diff_consecutive_bins() {
+    let data: Vec<f64> = (0..100).map(f64::from).collect();
+    let cache = canvas::Cache::new();
+    let layout = bar_layout(BarGraph::from_slice(&data, &cache).bins(10));
+
+    assert!(layout.deltas[0].is_nan());
+    assert!(layout.deltas[1..].iter().all(|delta| *delta == 10.0));
+}
+
+#[test]
+fn line_stable_y_keeps_the_full_range_when_zoomed() {
+    let data: Vec<f64> = (0..1000).map(f64::from).collect();
+    let cache = canvas::Cache::new();
+    let state = LineGraphState::new(Zoom::Value(1.0));
+
+    let zoomed = line_layout(&data, &state).unwrap();
+    assert_eq!((zoomed.min_value, zoomed.max_value), (950.0, 999.0));
+
+    let stable = LineGraph::from_slice(&data, &cache)
+        .stable_y(true)
+        .compute_layout(BOUNDS, &state)
+        .unwrap();
+    assert_eq!((stable.min_value, stable.max_value), (0.0, 999.0));
+}
+
+#[test]
+fn line_arrow_key_past_the_edge_pans_the_window() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let graph = LineGraph::from_slice(&data, &cache);
+    let bounds = Rectangle::new(Point::ORIGIN, BOUNDS);
+    let mut state = LineGraphState::new(Zoom::Value(5.0));
+    state.pan_absolute(123);
+    state.focused = true;
+    let before = graph.compute_layout(BOUNDS, &state).unwrap().window;
+    state.hovered_point = Some(before.len() - 1);
+
+    let event = iced::Event::Keyboard(keyboard::Event::KeyPressed {
+        key: keyboard::Key::Named(keyboard::key::Named::ArrowRight),
+        modified_key: keyboard::Key::Named(keyboard::key::Named::ArrowRight),
+        physical_key: keyboard::key::Physical::Code(keyboard::key::Code::ArrowRight),
+        location: keyboard::Location::Standard,
+        modifiers: keyboard::Modifiers::default(),
+        text: None,
+        repeat: false,
+    });
+    let action = canvas::Program::update(
+        &graph,
+        &mut state,
+        &event,
+        bounds,
+        mouse::Cursor::Unavailable,
+    );
+
+    let after = graph.compute_layout(BOUNDS, &state).unwrap();
+    assert_eq!(after.window, before.start + 1..before.end + 1);
+    let hovered = state.hovered_point.unwrap();
+    assert_eq!(after.window.start + hovered, before.end);
+    // Publishing redraws, and the cleared cache makes that redraw show the new window
+    let (message, _, _) = action.unwrap().into_inner();
+    assert!(matches!(message, Some(LineInteraction::PointHovered(i)) if i == hovered));
+}
+
+#[test]
+fn bar_hover_reports_the_layout_span() {
+    let data = series(1000);
+    let cache = canvas::Cache::new();
+    let graph = BarGraph::from_slice(&data, &cache).bins(30);
+    let bounds = Rectangle::new(Point::ORIGIN, BOUNDS);
+
+    for zoom in [Zoom::Full, Zoom::Value(3.0)] {
+        let mut state = BarGraphState::new(zoom);
+        let layout = graph.compute_layout(BOUNDS, &state).unwrap();
+        for (bar, rect) in layout.bars.iter().enumerate() {
+            let position = rect.center();
+            let event = iced::Event::Mouse(mouse::Event::CursorMoved { position });
+            let action = canvas::Program::update(
+                &graph,
+                &mut state,
+                &event,
+                bounds,
+                mouse::Cursor::Available(position),
+            );
+            let (message, _, _) = action.unwrap().into_inner();
+            let Some(BarInteraction::BarHovered { samples, .. }) = message else {
+                panic!("no hover for bar {bar}");
+            };
+            assert_eq!(
+                samples.map(BinSpan::Samples).as_ref(),
+                layout.spans.get(bar)
+            );
+        }
+    }
+}
+
+#[test]
+fn mean_std_dev_of_no_samples_is_nan() {
+    let (mean, std_dev) = mean_std_dev(&[]);
+    assert!(mean.is_nan());
+    assert!(std_dev.is_nan());
+}
+
+#[test]
+fn mean_std_dev_holds_up_at_a_large_offset() {
+    // Epoch milliseconds one apart: a one-pass sum of squares loses the spread
+    let data: Vec<f64> = (0..4).map(|i| 1.7e12 + i as f64).collect();
+    let (mean, std_dev) = mean_std_dev(&data);
+    assert_eq!(mean, 1.7e12 + 1.5);
+    assert!((std_dev - 1.25f64.sqrt()).abs() < 1e-9, "{std_dev}");
+}
@@ -0,0 +1,169 @@
+//! Property tests for the `Zoom` step state machine and view tokens
+
+use proptest::prelude::*;
+use snowline::line_graph::state::LineGraphState;
+use snowline::zoom::Zoom;
+
+/// Enough steps to walk from the minimum to any maximum used below (or back)
+const MAX_STEPS: usize = 100;
+
+fn limits() -> impl Strategy<Value = (f32, f32)> {
+    (0.05f32..0.5, 1.0f32..50.0)
+}
+
+/// A zoom between `min` and `max`, or full view
+fn zoom_within(min: f32, max: f32) -> impl Strategy<Value = Zoom> {
+    prop_oneof![
+        1 => Just(Zoom::Full),
+        4 => (min..=max).prop_map(Zoom::Value),
+    ]
+}
+
+fn limits_and_zoom() -> impl Strategy<Value = (f32, f32, Zoom)> {
+    limits().prop_flat_map(|(min, max)| (Just(min), Just(max), zoom_within(min, max)))
+}
+
+/// Order zoom levels from most zoomed out (full view) to most zoomed in
+fn level(zoom: Zoom) -> f32 {
+    match zoom {
+        Zoom::Full => f32::NEG_INFINITY,
+        Zoom::Value(value) => value,
+    }
+}
+
+proptest! {
+    #[test]
+    fn increment_never_exceeds_max((_min, max, zoom) in limits_and_zoom()) {
+        let next = zoom.increment_with_limits(max);
+        prop_assert!(next.is_value());
+        prop_assert!(level(next) <= max);
+    }
+
+    #[test]
+    fn increment_never_zooms_out((_min, max, zoom) in limits_and_zoom()) {
+        prop_assert!(level(zoom.increment_with_limits(max)) >= level(zoom));
+    }
+
+    #[test]
+    fn decrement_never_zooms_in((min, _max, zoom) in limits_and_zoom()) {
+        prop_assert!(level(zoom.decrement_with_limits(min)) <= level(zoom));
+    }
+
+    #[test]
+    fn decrement_stays_at_or_above_min_unless_full((min, _max, zoom) in limits_and_zoom()) {
+        match zoom.decrement_with_limits(min) {
+            Zoom::Full => {}
+            Zoom::Value(value) => prop_assert!(value >= min, "{value} < {min}"),
+        }
+    }
+
+    #[test]
+    fn increment_then_decrement_does_not_overshoot((min, max, zoom) in limits_and_zoom()) {
+        let up = zoom.increment_with_limits(max);
+        let back = up.decrement_with_limits(min);
+        prop_assert!(level(back) <= level(up));
+        // Stepping in from anywhere but full view and back out lands in view
+        if let Zoom::Value(value) = zoom {
+            prop_assert!(back.is_value(), "{value} came back as full view");
+            prop_assert!(level(back) <= value + 1e-4);
+        }
+    }
+
+    #[test]
+    fn repeated_increments_converge_to_max((_min, max, zoom) in limits_and_zoom()) {
+        let mut zoom = zoom;
+        for _ in 0..MAX_STEPS {
+            zoom = zoom.increment_with_limits(max);
+        }
+        prop_assert_eq!(zoom, Zoom::Value(max));
+        prop_assert_eq!(zoom.increment_with_limits(max), Zoom::Value(max));
+    }
+
+    #[test]
+    fn repeated_decrements_reach_full_view((min, _max, zoom) in limits_and_zoom()) {
+        let mut zoom = zoom;
+        for _ in 0..MAX_STEPS {
+            zoom = zoom.decrement_with_limits(min);
+        }
+        prop_assert_eq!(zoom, Zoom::Full);
+        prop_assert_eq!(zoom.decrement_with_limits(min), Zoom::Full);
+    }
+
+    #[test]
+    fn integer_steps_land_on_whole_levels((_min, max, zoom) in limits_and_zoom()) {
+        for next in [zoom.increment_integer(max), zoom.decrement_integer()] {
+            if let Zoom::Value(value) = next {
+                prop_assert!(value >= 1.0 && value.fract() == 0.0, "{value}");
+                prop_assert!(value <= max.max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn zooming_out_visits_min_before_full_view((min, max, zoom) in limits_and_zoom()) {
+        let mut previous = zoom;
+        let mut zoom = zoom;
+        for _ in 0..MAX_STEPS {
+            zoom = zoom.decrement_with_limits(min);
+            if zoom.is_full() {
+                break;
+            }
+            previous = zoom;
+        }
+        // Full view is only reached from the minimum level itself
+        if previous.is_value() && level(previous) <= max {
+            prop_assert!(level(previous) <= min + 1e-4, "{previous:?} skipped to full view");
+        }
+    }
+}
+
+#[test]
+fn full_view_steps_in_to_the_smallest_level() {
+    assert_eq!(Zoom::Full.increment(), Zoom::Value(0.1));
+    assert_eq!(Zoom::Full.decrement(), Zoom::Full);
+}
+
+#[test]
+fn default_limits_walk_the_documented_levels() {
+    let mut zoom = Zoom::Value(1.0);
+    for expected in 2..=10 {
+        zoom = zoom.increment();
+        assert_eq!(zoom, Zoom::Value(expected as f32));
+    }
+    assert_eq!(zoom.increment(), Zoom::Value(10.0));
+
+    let mut steps_out = 0;
+    while zoom.is_value() {
+        zoom = zoom.decrement();
+        steps_out += 1;
+    }
+    // 10 → 1 in whole steps, 1.0 → 0.1 in tenths, then full view
+    assert_eq!(steps_out, 9 + 9 + 1);
+}
+
+proptest! {
+    #[test]
+    fn view_token_round_trips(zoom in zoom_within(0.01, 50.0), pan in 0usize..3, start in 0usize..100_000) {
+        let mut state = LineGraphState::new(zoom);
+        match pan {
+            0 => state.pan_start(),
+            1 => state.pan_end(),
+            _ => state.pan_absolute(start),
+        }
+        let token = state.view_token();
+        prop_assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || ".-~".contains(c)));
+
+        let restored = LineGraphState::from_view_token(&token).unwrap();
+        prop_assert_eq!(restored.zoom, state.zoom);
+        prop_assert_eq!(restored.pan, state.pan);
+    }
+}
+
+#[test]
+fn malformed_view_tokens_are_rejected() {
+    for token in [
+        "", "f", "~e", "f~x", "abc~e", "2~a", "2~a-1", "NaN~s", "0~s", "-1~s",
+    ] {
+        assert!(LineGraphState::from_view_token(token).is_none(), "{token}");
+    }
+}